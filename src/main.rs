@@ -1,43 +1,72 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, encrypt, read, scaffold, solve, stars, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        commands::solve, export, limits::Limits, profile::Profile, report::Report, Day,
+    };
+    use std::{path::PathBuf, process};
 
     pub enum AppArguments {
         Download {
             day: Day,
+            account: Option<String>,
+            force: bool,
         },
         Read {
             day: Day,
         },
+        Encrypt,
+        GenerateKey,
+        Decrypt,
         Scaffold {
             day: Day,
             download: bool,
+            force: bool,
             overwrite: bool,
         },
         Solve {
             day: Day,
-            release: bool,
+            profile: Profile,
+            limits: Limits,
             dhat: bool,
-            submit: Option<u8>,
+            submit: Option<solve::Submit>,
+            test: bool,
+            watch: bool,
+        },
+        SolveAllInputs {
+            day: Day,
+            profile: Profile,
+            limits: Limits,
         },
         All {
-            release: bool,
+            profile: Profile,
+            limits: Limits,
+            reports: Vec<Report>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            history: Option<Day>,
+            export: Option<(export::Format, PathBuf)>,
+            profile: Profile,
+            limits: Limits,
+            reports: Vec<Report>,
+        },
+        Stars {
+            mark: Option<(Day, u8)>,
+            unmark: Option<(Day, u8)>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -45,37 +74,91 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+                profile: Profile::resolve(
+                    args.opt_value_from_str("--profile")?,
+                    args.contains("--release"),
+                ),
+                limits: parse_limits(&mut args)?,
+                reports: args.values_from_str("--report")?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let history = args.opt_value_from_str("--history")?;
+                let export_format: Option<export::Format> = args.opt_value_from_str("--export")?;
+                let profile = args
+                    .opt_value_from_str("--profile")?
+                    .unwrap_or_else(Profile::bench);
+                let limits = parse_limits(&mut args)?;
+                let reports = args.values_from_str("--report")?;
+                // the export path is a free argument that follows the format,
+                // it is read once all options are removed so it is the first remaining argument.
+                let export = match export_format {
+                    Some(format) => Some((format, args.free_from_str()?)),
+                    None => None,
+                };
+
+                if history.is_some() && export.is_some() {
+                    eprintln!("`--history` can not be combined with `--export`.");
+                    process::exit(1);
+                }
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    history,
+                    export,
+                    profile,
+                    limits,
+                    reports,
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                account: args.opt_value_from_str("--account")?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("encrypt") if args.contains("--generate-key") => AppArguments::GenerateKey,
+            Some("encrypt") => AppArguments::Encrypt,
+            Some("decrypt") => AppArguments::Decrypt,
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
+                force: args.contains("--force"),
                 overwrite: args.contains("--overwrite"),
             },
+            Some("solve") if args.contains("--all-inputs") => AppArguments::SolveAllInputs {
+                day: args.free_from_str()?,
+                profile: Profile::resolve(
+                    args.opt_value_from_str("--profile")?,
+                    args.contains("--release"),
+                ),
+                limits: parse_limits(&mut args)?,
+            },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
+                profile: Profile::resolve(
+                    args.opt_value_from_str("--profile")?,
+                    args.contains("--release"),
+                ),
+                limits: parse_limits(&mut args)?,
+                submit: parse_submit(&mut args)?,
                 dhat: args.contains("--dhat"),
+                test: args.contains("--test"),
+                watch: args.contains("--watch"),
+            },
+            Some("stars") => AppArguments::Stars {
+                mark: parse_part_flag(&mut args, "--mark")?,
+                unmark: parse_part_flag(&mut args, "--unmark")?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -93,6 +176,34 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse a flag that is followed by a day and a part, e.g. `--mark 1 2`.
+    fn parse_part_flag(
+        args: &mut pico_args::Arguments,
+        key: &'static str,
+    ) -> Result<Option<(Day, u8)>, pico_args::Error> {
+        match args.opt_value_from_str(key)? {
+            Some(day) => Ok(Some((day, args.free_from_str()?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Parse `--submit <part>`, optionally followed by `--wait` to wait out cooldowns.
+    fn parse_submit(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<solve::Submit>, pico_args::Error> {
+        let wait = args.contains("--wait");
+        Ok(args
+            .opt_value_from_str("--submit")?
+            .map(|part| solve::Submit { part, wait }))
+    }
+
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            memory_mb: args.opt_value_from_str("--memory-limit")?,
+            cpu_secs: args.opt_value_from_str("--cpu-limit")?,
+        })
+    }
 }
 
 fn main() {
@@ -102,43 +213,62 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::All {
+                profile,
+                limits,
+                reports,
+            } => all::handle(&profile, &limits, &reports),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                history,
+                export,
+                profile,
+                limits,
+                reports,
+            } => match (history, export) {
+                (Some(day), _) => time::print_history(day),
+                (None, Some((format, path))) => time::export(format, &path),
+                (None, None) => time::handle(day, all, store, &profile, &limits, &reports),
+            },
+            AppArguments::Download {
+                day,
+                account,
+                force,
+            } => download::handle(day, account.as_deref(), force),
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Encrypt => encrypt::handle(),
+            AppArguments::GenerateKey => encrypt::handle_generate_key(),
+            AppArguments::Decrypt => encrypt::handle_decrypt(),
             AppArguments::Scaffold {
                 day,
                 download,
+                force,
                 overwrite,
             } => {
                 scaffold::handle(day, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(day, None, force);
                 }
             }
             AppArguments::Solve {
                 day,
-                release,
+                profile,
+                limits,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                test,
+                watch,
+            } => solve::handle(day, &profile, &limits, dhat, submit, test, watch),
+            AppArguments::SolveAllInputs {
+                day,
+                profile,
+                limits,
+            } => solve::handle_all_inputs(day, &profile, &limits),
+            AppArguments::Stars { mark, unmark } => stars::handle(mark, unmark),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
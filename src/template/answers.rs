@@ -0,0 +1,177 @@
+/// Known-correct answers, used to check that benchmarked solutions still produce the right result.
+/// Answers are kept in `data/answers.json`, e.g. `{ "01": { "part_1": "11", "part_2": "31" } }`.
+/// JSON numbers are read as `f64`, so integers above 2^53 have to be written as strings.
+use std::{collections::HashMap, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// The largest integer that a JSON number represents exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// The known answers of a day, or `None` if there is no readable answers file.
+pub fn read_known(day: Day) -> Option<[Option<String>; 2]> {
+    fs::read_to_string(ANSWERS_FILE_PATH).ok().and_then(|s| {
+        parse_answers_file(&s, day)
+            .map_err(|e| eprintln!("Ignoring known answers of day {day}: {e}"))
+            .ok()
+    })
+}
+
+/// Integer answers are compared by value, e.g. `+42` matches `42`. Other answers are compared as trimmed text.
+fn normalize(answer: &str) -> String {
+    let answer = answer.trim();
+    answer
+        .parse::<i128>()
+        .map_or_else(|_| answer.to_string(), |n| n.to_string())
+}
+
+/// Check the answers of a day against the known answers.
+/// Returns `None` if there are no known answers for the day.
+pub fn verify_against(known: &[Option<String>; 2], answers: &[Option<String>; 2]) -> Option<bool> {
+    if known.iter().all(Option::is_none) {
+        return None;
+    }
+
+    Some(
+        known
+            .iter()
+            .zip(answers)
+            .all(|(known, answer)| match known {
+                Some(known) => answer.as_deref().map(normalize) == Some(normalize(known)),
+                None => true,
+            }),
+    )
+}
+
+/// Describe every part whose answer does not match its known answer, e.g. `expected 11, got 12`.
+pub fn mismatches(
+    known: &[Option<String>; 2],
+    answers: &[Option<String>; 2],
+) -> [Option<String>; 2] {
+    [0, 1].map(|i| {
+        let known = known[i].as_deref()?;
+        match answers[i].as_deref() {
+            Some(answer) if normalize(answer) == normalize(known) => None,
+            Some(answer) => Some(format!("expected {known}, got {answer}")),
+            None => Some(format!("expected {known}, got no answer")),
+        }
+    })
+}
+
+fn parse_answers_file(s: &str, day: Day) -> Result<[Option<String>; 2], String> {
+    let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+    let Some(entry) = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?
+        .get(&day.to_string())
+    else {
+        return Ok([None, None]);
+    };
+
+    let entry = entry
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected answers to be an object.")?;
+
+    // answers may be written as numbers or strings.
+    let read = |key: &str| match entry.get(key) {
+        Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+        #[allow(clippy::cast_possible_truncation)]
+        Some(JsonValue::Number(n)) if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER => {
+            Ok(Some((*n as i64).to_string()))
+        }
+        Some(JsonValue::Number(_)) => Err(format!(
+            "{key} is not an integer that a JSON number can hold, write it as a string."
+        )),
+        _ => Ok(None),
+    };
+
+    Ok([read("part_1")?, read("part_2")?])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{mismatches, parse_answers_file, verify_against};
+    use crate::day;
+
+    #[test]
+    fn parses_answers_file() {
+        let json = r#"{ "01": { "part_1": "11", "part_2": 31 }, "02": { "part_1": "2" } }"#;
+
+        assert_eq!(
+            parse_answers_file(json, day!(1)).unwrap(),
+            [Some("11".into()), Some("31".into())]
+        );
+        assert_eq!(
+            parse_answers_file(json, day!(2)).unwrap(),
+            [Some("2".into()), None]
+        );
+        assert_eq!(parse_answers_file(json, day!(3)).unwrap(), [None, None]);
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let known = [Some("11".into()), None];
+
+        assert_eq!(
+            verify_against(&known, &[Some("11".into()), Some("31".into())]),
+            Some(true)
+        );
+        assert_eq!(
+            verify_against(&known, &[Some("12".into()), None]),
+            Some(false)
+        );
+        assert_eq!(verify_against(&known, &[None, None]), Some(false));
+        assert_eq!(
+            verify_against(&[None, None], &[Some("11".into()), None]),
+            None
+        );
+    }
+
+    #[test]
+    fn describes_mismatches() {
+        let known = [Some("11".into()), Some("31".into())];
+
+        assert_eq!(
+            mismatches(&known, &[Some("11".into()), Some("31".into())]),
+            [None, None]
+        );
+        assert_eq!(
+            mismatches(&known, &[Some("12".into()), None]),
+            [
+                Some("expected 11, got 12".into()),
+                Some("expected 31, got no answer".into())
+            ]
+        );
+        assert_eq!(
+            mismatches(&[None, None], &[Some("12".into()), None]),
+            [None, None]
+        );
+    }
+
+    #[test]
+    fn compares_large_integers_exactly() {
+        let json = r#"{ "01": { "part_1": "9007199254740993", "part_2": 9007199254740993 } }"#;
+        let err = parse_answers_file(json, day!(1)).unwrap_err();
+        assert!(err.contains("write it as a string"));
+
+        let known = [Some("9007199254740993".into()), None];
+        assert_eq!(
+            verify_against(&known, &[Some("9007199254740992".into()), None]),
+            Some(false)
+        );
+        assert_eq!(
+            verify_against(&known, &[Some("9007199254740993".into()), None]),
+            Some(true)
+        );
+        assert_eq!(
+            verify_against(&[Some("042".into()), None], &[Some("42".into()), None]),
+            Some(true)
+        );
+    }
+}
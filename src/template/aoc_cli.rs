@@ -1,7 +1,10 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
-    process::{Command, Output, Stdio},
+    fs, io,
+    path::Path,
+    process::{self, Command, Output, Stdio},
 };
 
 use crate::template::Day;
@@ -11,6 +14,7 @@ pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    IO(io::Error),
 }
 
 impl Display for AocCommandError {
@@ -21,6 +25,7 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::IO(e) => write!(f, "could not read the output of aoc-cli: {e}"),
         }
     }
 }
@@ -33,62 +38,75 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
+/// The puzzle input of a day, downloaded by aoc-cli with the session in `session_file` if given.
+pub fn input(day: Day, session_file: Option<&Path>) -> Result<String, AocCommandError> {
+    fetch(
         day,
-    );
-
-    call_aoc_cli(&args)
+        "--input-only",
+        "--input-file",
+        "input.txt",
+        session_file,
+    )
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
+/// The puzzle description of a day as markdown, downloaded by aoc-cli.
+pub fn puzzle(day: Day) -> Result<String, AocCommandError> {
+    fetch(day, "--puzzle-only", "--puzzle-file", "puzzle.md", None)
+}
 
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+/// Let aoc-cli download to a temporary file and return its contents.
+fn fetch(
+    day: Day,
+    only: &str,
+    file_flag: &str,
+    name: &str,
+    session_file: Option<&Path>,
+) -> Result<String, AocCommandError> {
+    let path = env::temp_dir().join(format!("aoc-{}-{day}-{name}", process::id()));
+
+    let mut args: Vec<String> = vec![];
+    if let Some(session_file) = session_file {
+        args.push("--session-file".into());
+        args.push(session_file.to_string_lossy().into());
+    }
+    args.extend([
+        "--overwrite".into(),
+        only.into(),
+        file_flag.into(),
+        path.to_string_lossy().into(),
+    ]);
+
+    let args = build_args("download", &args, day);
+
+    call_aoc_cli(&args)?;
+    let contents = fs::read_to_string(&path).map_err(AocCommandError::IO);
+    let _ = fs::remove_file(&path);
+    contents
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submit an answer and return the response of aoc-cli, which callers print.
+pub fn submit(day: Day, part: u8, result: &str) -> Result<String, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
-}
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
-}
+    // capture stdout so that callers can check whether the answer was accepted.
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
-fn get_year() -> Option<u16> {
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -0,0 +1,386 @@
+/// Native HTTP client for adventofcode.com, used instead of shelling out to aoc-cli.
+/// Authenticates with the `session` cookie of a logged in browser, read from `AOC_SESSION` or a session file.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{
+    aoc_cli::get_year,
+    inputs::{self, InvalidInput},
+    puzzle_html, Day,
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// See: https://www.reddit.com/r/adventofcode/comments/z9dhtd/please_include_your_contact_info_in_the_useragent/
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found for the default account, or for the named one.
+    SessionNotFound(Option<String>),
+    YearNotSet,
+    /// The session cookie is missing, invalid or expired.
+    Unauthorized,
+    /// Too many requests or answers were sent, with the reason given by the server if any.
+    RateLimited(Option<String>),
+    HttpStatus(u16),
+    /// The response is an error or login page rather than a puzzle input.
+    InvalidInput(InvalidInput),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound(None) => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or save the cookie to `~/.adventofcode.session`."
+            ),
+            AocClientError::SessionNotFound(Some(account)) => write!(
+                f,
+                "no session cookie found for account `{account}`. Set `{}` or save the cookie to `~/.adventofcode.{account}.session`.",
+                session_var(Some(account))
+            ),
+            AocClientError::YearNotSet => write!(f, "`AOC_YEAR` is not set."),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected, it may have expired.")
+            }
+            AocClientError::RateLimited(Some(reason)) => write!(f, "rate limited: {reason}"),
+            AocClientError::RateLimited(None) => write!(f, "rate limited, try again later."),
+            AocClientError::HttpStatus(status) => {
+                write!(f, "adventofcode.com responded with status {status}.")
+            }
+            AocClientError::InvalidInput(e) => write!(f, "{e}"),
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(400 | 401 | 403, _) => AocClientError::Unauthorized,
+            ureq::Error::Status(429, response) => AocClientError::RateLimited(
+                response
+                    .header("Retry-After")
+                    .map(|secs| format!("retry after {secs} seconds.")),
+            ),
+            // inputs of locked puzzles are answered with a 404 that explains why.
+            ureq::Error::Status(404, response) => response
+                .into_string()
+                .ok()
+                .and_then(|body| inputs::validate(&body).err())
+                .filter(|e| *e != InvalidInput::Empty)
+                .map_or(
+                    AocClientError::HttpStatus(404),
+                    AocClientError::InvalidInput,
+                ),
+            ureq::Error::Status(status, _) => AocClientError::HttpStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// Read the session cookie from `AOC_SESSION`, or from the file at `AOC_SESSION_FILE`,
+/// which defaults to `~/.adventofcode.session` like it does for aoc-cli.
+/// Named accounts read `AOC_SESSION_<NAME>` or `~/.adventofcode.<name>.session` instead.
+pub fn read_session(account: Option<&str>) -> Result<String, AocClientError> {
+    if let Ok(session) = env::var(session_var(account)) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+
+    session_file(account)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+        .ok_or_else(|| AocClientError::SessionNotFound(account.map(String::from)))
+}
+
+/// The environment variable that holds the session cookie of an account.
+fn session_var(account: Option<&str>) -> String {
+    match account {
+        Some(account) => format!("AOC_SESSION_{}", account.to_uppercase().replace('-', "_")),
+        None => "AOC_SESSION".into(),
+    }
+}
+
+/// The file that holds the session cookie of an account.
+pub fn session_file(account: Option<&str>) -> Option<PathBuf> {
+    let home = || {
+        env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(PathBuf::from)
+    };
+
+    match account {
+        Some(account) => home().map(|home| home.join(format!(".adventofcode.{account}.session"))),
+        None => env::var_os("AOC_SESSION_FILE")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".adventofcode.session"))),
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        // redirects are not followed, adventofcode.com redirects to the login page when not authenticated.
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(TIMEOUT)
+            .redirects(0)
+            .build();
+
+        AocClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            year,
+            agent,
+        }
+    }
+
+    /// Create a client for `AOC_YEAR` that is logged in to an account, talking to `AOC_BASE_URL` if set.
+    pub fn from_env(account: Option<&str>) -> Result<Self, AocClientError> {
+        let year = get_year().ok_or(AocClientError::YearNotSet)?;
+        let session = read_session(account)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(AocClient::new(&base_url, &session, year))
+    }
+
+    /// The personal puzzle input of a day. Fails if the response is not a puzzle input.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        let input = self.get(&format!("{}/input", self.day_path(day)))?;
+        inputs::validate(&input).map_err(AocClientError::InvalidInput)?;
+        Ok(input)
+    }
+
+    /// The puzzle description of a day as markdown. Part two is only included once part one is solved.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_path(day))?;
+        Ok(puzzle_html::to_markdown(&html))
+    }
+
+    /// Submit an answer and return the message of the response.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_path(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        let html = read_body(response)?;
+        let message = puzzle_html::article_text(&html).unwrap_or(html);
+
+        if message.contains("You gave an answer too recently") {
+            return Err(AocClientError::RateLimited(Some(message)));
+        }
+
+        Ok(message)
+    }
+
+    fn day_path(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self.agent.get(url).set("Cookie", &self.cookie()).call()?;
+        read_body(response)
+    }
+}
+
+fn read_body(response: ureq::Response) -> Result<String, AocClientError> {
+    if (300..400).contains(&response.status()) {
+        return Err(AocClientError::Unauthorized);
+    }
+    Ok(response.into_string()?)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::{AocClient, AocClientError, InvalidInput};
+    use crate::{day, template::submissions::Outcome};
+
+    /// Serve a single canned response on a local port, returning the base URL and the received request.
+    fn serve_once(status: &str, headers: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n{headers}\r\n{body}",
+            body.len()
+        );
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8_lossy(&body));
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_input_with_session() {
+        let (base_url, server) = serve_once("200 OK", "", "1 2\n3 4\n");
+        let client = AocClient::new(&base_url, "abc123", 2024);
+
+        assert_eq!(client.input(day!(1)).unwrap(), "1 2\n3 4\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains("session=abc123"));
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "",
+            "<main><article><h2>--- Day 1 ---</h2><p>Hi.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "abc123", 2024);
+
+        assert_eq!(client.puzzle(day!(1)).unwrap(), "## --- Day 1 ---\n\nHi.");
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "abc123", 2024);
+
+        let message = client.submit(day!(5), 2, "42").unwrap();
+        assert_eq!(Outcome::from_message(&message), Some(Outcome::Correct));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/5/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn maps_errors() {
+        let (base_url, server) = serve_once("400 Bad Request", "", "Please log in.");
+        let client = AocClient::new(&base_url, "expired", 2024);
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::Unauthorized)
+        ));
+        server.join().unwrap();
+
+        let (base_url, server) = serve_once("302 Found", "Location: /auth/login\r\n", "");
+        let client = AocClient::new(&base_url, "expired", 2024);
+        assert!(matches!(
+            client.submit(day!(1), 1, "42"),
+            Err(AocClientError::Unauthorized)
+        ));
+        server.join().unwrap();
+
+        let (base_url, server) = serve_once("429 Too Many Requests", "Retry-After: 60\r\n", "");
+        let client = AocClient::new(&base_url, "abc123", 2024);
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::RateLimited(Some(_)))
+        ));
+        server.join().unwrap();
+
+        let (base_url, server) = serve_once("404 Not Found", "", "");
+        let client = AocClient::new(&base_url, "abc123", 2024);
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::HttpStatus(404))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "",
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+        );
+        let client = AocClient::new(&base_url, "", 2024);
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::InvalidInput(InvalidInput::LoggedOut))
+        ));
+        server.join().unwrap();
+
+        let (base_url, server) = serve_once(
+            "404 Not Found",
+            "",
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        );
+        let client = AocClient::new(&base_url, "abc123", 2024);
+        assert!(matches!(
+            client.input(day!(25)),
+            Err(AocClientError::InvalidInput(InvalidInput::NotUnlocked))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn detects_answer_cooldowns() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "",
+            "<article><p>You gave an answer too recently. You have 41s left to wait.</p></article>",
+        );
+        let client = AocClient::new(&base_url, "abc123", 2024);
+        assert!(matches!(
+            client.submit(day!(1), 1, "42"),
+            Err(AocClientError::RateLimited(Some(_)))
+        ));
+        server.join().unwrap();
+    }
+}
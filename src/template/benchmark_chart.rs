@@ -0,0 +1,266 @@
+/// Module that renders benchmark timings as a hand-written SVG bar chart.
+/// Every day gets one bar per part, so days that dominate the total stand out at a glance.
+use std::{env, fmt::Write};
+
+use crate::template::timings::{format_nanos, Timings};
+
+pub static CHART_PATH: &str = "./.assets/benchmarks.svg";
+
+const WIDTH: f64 = 800.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 90.0;
+const MARGIN_TOP: f64 = 50.0;
+const MARGIN_BOTTOM: f64 = 30.0;
+const BAR_HEIGHT: f64 = 10.0;
+const BAR_GAP: f64 = 2.0;
+const ROW_GAP: f64 = 10.0;
+
+const PART_COLORS: [&str; 2] = ["#4f8cc9", "#e6a23c"];
+const TEXT_COLOR: &str = "#888888";
+const GRID_COLOR: &str = "#88888844";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scale {
+    Linear,
+    Log,
+}
+
+impl Scale {
+    /// Read the scale from the `AOC_CHART_SCALE` environment variable, defaults to a linear scale.
+    pub fn from_env() -> Self {
+        match env::var("AOC_CHART_SCALE").as_deref() {
+            Ok("log") => Scale::Log,
+            _ => Scale::Linear,
+        }
+    }
+}
+
+/// Maps durations to horizontal positions in the plot area.
+struct Axis {
+    scale: Scale,
+    min: f64,
+    max: f64,
+    width: f64,
+}
+
+impl Axis {
+    fn new(scale: Scale, values: &[f64], width: f64) -> Self {
+        let max_value = values.iter().copied().fold(1_f64, f64::max);
+
+        match scale {
+            Scale::Linear => Self {
+                scale,
+                min: 0.0,
+                max: max_value,
+                width,
+            },
+            Scale::Log => {
+                let min_value = values.iter().copied().fold(max_value, f64::min).max(1.0);
+                let min = 10_f64.powf(min_value.log10().floor());
+                let max = 10_f64.powf(max_value.log10().ceil());
+                Self {
+                    scale,
+                    min,
+                    max: if max > min { max } else { min * 10.0 },
+                    width,
+                }
+            }
+        }
+    }
+
+    fn position(&self, value: f64) -> f64 {
+        let ratio = match self.scale {
+            Scale::Linear => value / self.max,
+            Scale::Log => {
+                (value.max(self.min).log10() - self.min.log10())
+                    / (self.max.log10() - self.min.log10())
+            }
+        };
+        (ratio.clamp(0.0, 1.0) * self.width).max(1.0)
+    }
+
+    fn ticks(&self) -> Vec<f64> {
+        match self.scale {
+            Scale::Linear => (0..=4).map(|i| self.max * f64::from(i) / 4.0).collect(),
+            Scale::Log => {
+                let mut ticks = vec![];
+                let mut tick = self.min;
+                while tick <= self.max * 1.000_001 {
+                    ticks.push(tick);
+                    tick *= 10.0;
+                }
+                ticks
+            }
+        }
+    }
+}
+
+/// A formatted duration and its value in nanoseconds.
+type Bar = (String, f64);
+
+/// Render the timings as an SVG document.
+pub fn render(timings: &Timings, scale: Scale) -> String {
+    let rows: Vec<(u8, [Option<Bar>; 2])> = timings
+        .data
+        .iter()
+        .map(|timing| {
+            let part = |s: &Option<String>, nanos: Option<f64>| s.clone().zip(nanos);
+            (
+                timing.day.into_inner(),
+                [
+                    part(&timing.part_1, timing.part_1_nanos),
+                    part(&timing.part_2, timing.part_2_nanos),
+                ],
+            )
+        })
+        .collect();
+
+    let values: Vec<f64> = rows
+        .iter()
+        .flat_map(|(_, parts)| parts.iter().flatten().map(|(_, nanos)| *nanos))
+        .collect();
+
+    let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let row_height = 2.0 * BAR_HEIGHT + BAR_GAP + ROW_GAP;
+    #[allow(clippy::cast_precision_loss)]
+    let plot_height = rows.len() as f64 * row_height;
+    let height = MARGIN_TOP + plot_height + MARGIN_BOTTOM;
+
+    let axis = Axis::new(scale, &values, plot_width);
+
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="sans-serif" font-size="12">"#
+    );
+
+    let scale_label = match scale {
+        Scale::Linear => "",
+        Scale::Log => " (log scale)",
+    };
+    let _ = writeln!(
+        svg,
+        r#"  <text x="{MARGIN_LEFT}" y="18" fill="{TEXT_COLOR}" font-weight="bold">Benchmarks{scale_label}</text>"#
+    );
+
+    for (i, color) in PART_COLORS.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let x = MARGIN_LEFT + i as f64 * 80.0;
+        let _ = writeln!(
+            svg,
+            r#"  <rect x="{x}" y="28" width="10" height="10" fill="{color}" />"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{}" y="37" fill="{TEXT_COLOR}">Part {}</text>"#,
+            x + 14.0,
+            i + 1
+        );
+    }
+
+    for tick in axis.ticks() {
+        let x = MARGIN_LEFT + axis.position(tick);
+        let _ = writeln!(
+            svg,
+            r#"  <line x1="{x:.1}" y1="{MARGIN_TOP}" x2="{x:.1}" y2="{:.1}" stroke="{GRID_COLOR}" />"#,
+            MARGIN_TOP + plot_height
+        );
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{x:.1}" y="{:.1}" fill="{TEXT_COLOR}" text-anchor="middle">{}</text>"#,
+            height - 10.0,
+            format_nanos(tick)
+        );
+    }
+
+    for (i, (day, parts)) in rows.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = MARGIN_TOP + i as f64 * row_height;
+
+        let _ = writeln!(
+            svg,
+            r#"  <text x="{:.1}" y="{:.1}" fill="{TEXT_COLOR}" text-anchor="end">Day {day}</text>"#,
+            MARGIN_LEFT - 8.0,
+            y + BAR_HEIGHT + BAR_GAP / 2.0 + 4.0
+        );
+
+        for (part, value) in parts.iter().enumerate() {
+            let Some((label, nanos)) = value else {
+                continue;
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            let bar_y = y + part as f64 * (BAR_HEIGHT + BAR_GAP);
+            let bar_width = axis.position(*nanos);
+
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{MARGIN_LEFT}" y="{bar_y:.1}" width="{bar_width:.1}" height="{BAR_HEIGHT}" fill="{}"><title>Day {day}, Part {}: {label}</title></rect>"#,
+                PART_COLORS[part],
+                part + 1
+            );
+            let _ = writeln!(
+                svg,
+                r#"  <text x="{:.1}" y="{:.1}" fill="{TEXT_COLOR}" font-size="10">{label}</text>"#,
+                MARGIN_LEFT + bar_width + 4.0,
+                bar_y + BAR_HEIGHT - 1.0
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Axis, Scale};
+    use crate::{
+        day,
+        template::timings::{mock_timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                mock_timing(day!(1), Some("10µs"), Some("20ms")),
+                mock_timing(day!(4), Some("400ns"), None),
+            ],
+            machines: vec![],
+        }
+    }
+
+    #[test]
+    fn renders_bars_per_part() {
+        let svg = render(&get_mock_timings(), Scale::Linear);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert!(svg.contains("Day 1, Part 2: 20ms"));
+        assert!(svg.contains(">Day 4</text>"));
+        assert!(!svg.contains("log scale"));
+    }
+
+    #[test]
+    fn renders_log_scale() {
+        let svg = render(&get_mock_timings(), Scale::Log);
+        assert!(svg.contains("Benchmarks (log scale)"));
+        // ticks for every power of ten between 100ns and 100ms.
+        assert!(svg.contains(">100.0ns</text>"));
+        assert!(svg.contains(">100.0ms</text>"));
+    }
+
+    #[test]
+    fn maps_values_to_positions() {
+        let axis = Axis::new(Scale::Linear, &[50.0, 100.0], 200.0);
+        assert_eq!(axis.position(50.0), 100.0);
+        assert_eq!(axis.position(100.0), 200.0);
+
+        let axis = Axis::new(Scale::Log, &[10.0, 1000.0], 200.0);
+        assert_eq!(axis.position(100.0), 100.0);
+        assert_eq!(axis.ticks(), vec![10.0, 100.0, 1000.0]);
+    }
+}
@@ -0,0 +1,174 @@
+/// Module that compares benchmark timings against a time budget for the whole calendar.
+/// The default budget is one second for all days, split evenly into 40ms per day.
+use std::env;
+
+use crate::template::{table::print_table, timings::Timings, ANSI_BOLD, ANSI_RESET};
+
+const DEFAULT_TOTAL_MS: f64 = 1000.0;
+const DAYS: f64 = 25.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Budget {
+    pub total_ms: f64,
+    pub day_ms: f64,
+}
+
+impl Budget {
+    /// Read the budget from `AOC_BUDGET_MS` and `AOC_DAY_BUDGET_MS`.
+    /// The per-day budget defaults to an even split of the total.
+    pub fn from_env() -> Self {
+        let read = |key: &str| {
+            env::var(key)
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+        };
+
+        let total_ms = read("AOC_BUDGET_MS").unwrap_or(DEFAULT_TOTAL_MS);
+
+        Budget {
+            total_ms,
+            day_ms: read("AOC_DAY_BUDGET_MS").unwrap_or(total_ms / DAYS),
+        }
+    }
+}
+
+/// One row per day in day order, with its share of the total, the cumulative sum and whether it exceeds the budget.
+fn construct_rows(timings: &Timings, budget: &Budget) -> Vec<[String; 5]> {
+    let total_millis = timings.total_millis();
+
+    let mut data: Vec<_> = timings.data.iter().collect();
+    data.sort_by_key(|t| t.day);
+
+    let mut rows: Vec<[String; 5]> = vec![[
+        "Day".into(),
+        "Total".into(),
+        "Share".into(),
+        "Cumulative".into(),
+        "Budget".into(),
+    ]];
+
+    let mut cumulative = 0_f64;
+
+    for timing in data {
+        let millis = timing.total_nanos / 1_000_000_f64;
+        cumulative += millis;
+
+        let share = if total_millis > 0.0 {
+            format!("{:.1}%", millis / total_millis * 100.0)
+        } else {
+            "-".into()
+        };
+
+        let status = if millis > budget.day_ms {
+            format!("⚠️ over by {:.2}ms", millis - budget.day_ms)
+        } else {
+            "ok".into()
+        };
+
+        rows.push([
+            timing.day.to_string(),
+            format!("{millis:.2}ms"),
+            share,
+            format!("{cumulative:.2}ms"),
+            status,
+        ]);
+    }
+
+    rows
+}
+
+fn summary(timings: &Timings, budget: &Budget) -> String {
+    let total_millis = timings.total_millis();
+    let used = total_millis / budget.total_ms * 100.0;
+
+    if total_millis > budget.total_ms {
+        format!(
+            "Total: {total_millis:.2}ms, over the {:.2}ms budget by {:.2}ms ({used:.1}%).",
+            budget.total_ms,
+            total_millis - budget.total_ms
+        )
+    } else {
+        format!(
+            "Total: {total_millis:.2}ms of the {:.2}ms budget ({used:.1}%).",
+            budget.total_ms
+        )
+    }
+}
+
+/// Print how the timings of all benched days add up against the budget.
+pub fn print(timings: &Timings, budget: &Budget) {
+    if timings.data.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}Budget{ANSI_RESET} ({:.2}ms per day, {:.2}ms total)",
+        budget.day_ms, budget.total_ms
+    );
+    println!();
+
+    print_table(&construct_rows(timings, budget));
+
+    println!();
+    println!("{}", summary(timings, budget));
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{construct_rows, summary, Budget};
+    use crate::{
+        day,
+        template::timings::{mock_timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                mock_timing(day!(3), Some("60ms"), None),
+                mock_timing(day!(1), Some("20ms"), None),
+                mock_timing(day!(2), Some("20ms"), None),
+            ],
+            machines: vec![],
+        }
+    }
+
+    const BUDGET: Budget = Budget {
+        total_ms: 1000.0,
+        day_ms: 40.0,
+    };
+
+    #[test]
+    fn constructs_rows_in_day_order() {
+        let rows = construct_rows(&get_mock_timings(), &BUDGET);
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[1],
+            ["01", "20.00ms", "20.0%", "20.00ms", "ok"].map(String::from)
+        );
+        assert_eq!(
+            rows[3],
+            ["03", "60.00ms", "60.0%", "100.00ms", "⚠️ over by 20.00ms"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn summarizes_total() {
+        assert_eq!(
+            summary(&get_mock_timings(), &BUDGET),
+            "Total: 100.00ms of the 1000.00ms budget (10.0%)."
+        );
+
+        let budget = Budget {
+            total_ms: 50.0,
+            day_ms: 2.0,
+        };
+        assert_eq!(
+            summary(&get_mock_timings(), &budget),
+            "Total: 100.00ms, over the 50.00ms budget by 50.00ms (200.0%)."
+        );
+    }
+}
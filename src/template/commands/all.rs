@@ -1,5 +1,7 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{
+    all_days, limits::Limits, profile::Profile, report::Report, run_multi::run_multi,
+};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(profile: &Profile, limits: &Limits, reports: &[Report]) {
+    run_multi(&all_days().collect(), profile, false, limits, reports);
 }
@@ -1,14 +1,71 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{
+    inputs,
+    provider::{self, PuzzleProvider},
+    puzzle_examples, Day,
+};
+use std::{process, thread, time::Duration};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Delays between download attempts of a puzzle that was just unlocked.
+const RETRY_DELAYS_SECS: [u64; 6] = [2, 4, 8, 16, 32, 60];
+
+/// Download a day, either for the default account or only the input of a named account.
+pub fn handle(day: Day, account: Option<&str>, force: bool) {
+    if let Some(account) = account.filter(|account| !inputs::is_valid_account(account)) {
+        eprintln!(
+            "invalid account name `{account}`, only letters, digits, `-` and `_` are allowed."
+        );
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let provider = get_provider(account);
+
+    if let Err(e) = provider.download(day, account, force) {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
     };
+
+    if account.is_none() {
+        puzzle_examples::update(day);
+    }
+}
+
+/// Download a puzzle right after it unlocked, retrying with backoff while it is not available yet.
+pub fn handle_with_retry(day: Day) {
+    let provider = get_provider(None);
+    let mut delays = RETRY_DELAYS_SECS
+        .iter()
+        .map(|secs| Duration::from_secs(*secs));
+
+    loop {
+        match provider.download(day, None, false) {
+            Ok(()) => break,
+            Err(e) if e.is_transient() => {
+                let Some(delay) = delays.next() else {
+                    eprintln!("failed to download day {day}: {e}");
+                    process::exit(1);
+                };
+                eprintln!(
+                    "Day {day} is not available yet: {e} Retrying in {}s...",
+                    delay.as_secs()
+                );
+                thread::sleep(delay);
+            }
+            Err(e) => {
+                eprintln!("failed to download day {day}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    puzzle_examples::update(day);
+}
+
+fn get_provider(account: Option<&str>) -> Box<dyn PuzzleProvider> {
+    match provider::from_env(account) {
+        Ok(provider) => provider,
+        Err(e) => {
+            eprintln!("failed to set up the puzzle provider: {e}");
+            process::exit(1);
+        }
+    }
 }
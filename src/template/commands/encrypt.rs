@@ -0,0 +1,116 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::encryption::{self, Cipher, KEY_ENV};
+
+/// Encrypt the inputs and puzzles that were downloaded before a key was set up.
+pub fn handle() {
+    migrate(true);
+}
+
+/// Decrypt the inputs and puzzles, e.g. to stop encrypting them or to change the key.
+pub fn handle_decrypt() {
+    migrate(false);
+}
+
+/// Write a new random key to the key file. An existing key file is never replaced.
+pub fn handle_generate_key() {
+    let path = encryption::key_file_path();
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    // NOTE: only the owner may read the key.
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let result = options
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", encryption::generate_key()));
+
+    match result {
+        Ok(()) => println!(
+            "🔑 Wrote a new encryption key to \"{path}\". Share it with `{KEY_ENV}` in CI, never commit it."
+        ),
+        Err(e) => {
+            eprintln!("Failed to write the encryption key to \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Encrypt or decrypt every file in `data/inputs` and `data/puzzles` in place.
+/// Files that already are in the requested state are left alone.
+fn migrate(encrypt: bool) {
+    let cipher = match Cipher::from_env() {
+        Ok(Some(cipher)) => cipher,
+        Ok(None) => {
+            eprintln!(
+                "No encryption key found, set `{KEY_ENV}` or run `cargo encrypt --generate-key`."
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to read the encryption key: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut migrated = 0;
+    let mut failed = 0;
+
+    for path in files() {
+        match migrate_file(&cipher, &path, encrypt) {
+            Ok(true) => migrated += 1,
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to migrate \"{}\": {e}", path.display());
+                failed += 1;
+            }
+        }
+    }
+
+    if encrypt {
+        println!("🔒 Encrypted {migrated} file(s) in \"data/inputs\" and \"data/puzzles\".");
+    } else {
+        println!("🔓 Decrypted {migrated} file(s) in \"data/inputs\" and \"data/puzzles\".");
+    }
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Returns whether the file was changed.
+fn migrate_file(cipher: &Cipher, path: &Path, encrypt: bool) -> Result<bool, String> {
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+
+    let migrated = match (encrypt, encryption::is_encrypted(&data)) {
+        (true, false) => cipher.encrypt(&data),
+        (false, true) => cipher.decrypt(&data).map_err(|e| e.to_string())?,
+        _ => return Ok(false),
+    };
+
+    fs::write(path, migrated).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+fn files() -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = [("data/inputs", "txt"), ("data/puzzles", "md")]
+        .iter()
+        .filter_map(|(dir, extension)| {
+            fs::read_dir(dir).ok().map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.extension().is_some_and(|e| e == *extension))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .flatten()
+        .collect();
+
+    files.sort();
+    files
+}
@@ -1,15 +1,42 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{
+    encryption,
+    progress::Progress,
+    provider,
+    puzzle_examples::{self, PART_TWO_HEADING},
+    puzzle_render, Day,
+};
 
+/// Print the puzzle description of a day from `data/puzzles`.
+/// The puzzle is only fetched if it was not downloaded yet, or if part two was unlocked since.
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let cached = encryption::read_to_string(format!("data/puzzles/{day}.md")).ok();
+
+    let is_outdated = cached.as_ref().is_none_or(|puzzle| {
+        !puzzle.contains(PART_TWO_HEADING) && Progress::read_from_file().is_accepted(day, 1)
+    });
+
+    let puzzle = match cached {
+        Some(puzzle) if !is_outdated => puzzle,
+        cached => match provider::from_env(None).and_then(|provider| provider.save_puzzle(day)) {
+            Ok(puzzle) => {
+                puzzle_examples::update(day);
+                puzzle
+            }
+            Err(e) => {
+                let Some(puzzle) = cached else {
+                    eprintln!("failed to read day {day}: {e}");
+                    process::exit(1);
+                };
+                eprintln!("Could not refresh the puzzle of day {day}, showing the saved copy: {e}");
+                puzzle
+            }
+        },
     };
+
+    println!(
+        "{}",
+        puzzle_render::render(&puzzle, puzzle_render::terminal_width())
+    );
 }
@@ -1,8 +1,117 @@
-use std::process::{Command, Stdio};
+use std::{
+    fs,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+    process::{self, Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
 
-use crate::template::Day;
+use crate::template::{
+    limits::Limits, profile::Profile, run_multi, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const LIMIT_EXCEEDED_EXIT_CODE: i32 = 3;
+
+/// Submit the answer of a part, optionally waiting for a cooldown to pass first.
+pub struct Submit {
+    pub part: u8,
+    pub wait: bool,
+}
+
+pub fn handle(
+    day: Day,
+    profile: &Profile,
+    limits: &Limits,
+    dhat: bool,
+    submit: Option<Submit>,
+    test: bool,
+    watch: bool,
+) {
+    // the test harness does not run `main` of a solution, which is where limits are applied.
+    if test && !limits.is_empty() {
+        eprintln!("`--memory-limit` and `--cpu-limit` can not be combined with `--test`.");
+        process::exit(1);
+    }
+
+    let limits = if test {
+        Limits::default()
+    } else {
+        limits.for_day(day)
+    };
+
+    let cmd_args = if test {
+        build_test_args(day, profile)
+    } else {
+        build_run_args(day, profile, dhat, submit.as_ref())
+    };
+
+    if watch {
+        if submit.is_some() {
+            eprintln!("`--submit` can not be combined with `--watch`.");
+            process::exit(1);
+        }
+        watch_and_run(day, profile, &limits, &cmd_args, test);
+    } else if limits.is_empty() {
+        let mut cmd = Command::new("cargo");
+        profile.apply_env(&mut cmd);
+
+        let mut cmd = cmd
+            .args(&cmd_args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .unwrap();
+
+        cmd.wait().unwrap();
+    } else {
+        run_limited(profile, &limits, &cmd_args);
+    }
+}
+
+/// Run the command with resource limits, forwarding stderr to detect whether a limit was hit.
+fn run_limited(profile: &Profile, limits: &Limits, cmd_args: &[String]) {
+    let mut cmd = Command::new("cargo");
+    profile.apply_env(&mut cmd);
+    limits.apply_env(&mut cmd);
+
+    let mut cmd = cmd
+        .args(cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let stderr: Vec<String> = cmd
+        .stderr
+        .take()
+        .map(|stderr| {
+            BufReader::new(stderr)
+                .lines()
+                .map_while(Result::ok)
+                .inspect(|line| eprintln!("{line}"))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let status = cmd.wait().unwrap();
+
+    if !status.success() {
+        if let Some(kind) = limits.exceeded(&stderr) {
+            eprintln!("Limit exceeded: {}", limits.describe(kind));
+            process::exit(LIMIT_EXCEEDED_EXIT_CODE);
+        }
+    }
+}
+
+/// Run a day against the inputs of every account and print the answers side by side.
+pub fn handle_all_inputs(day: Day, profile: &Profile, limits: &Limits) {
+    run_multi::run_inputs(day, profile, limits);
+}
+
+fn build_run_args(day: Day, profile: &Profile, dhat: bool, submit: Option<&Submit>) -> Vec<String> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,23 +121,146 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        cmd_args.extend(profile.cargo_args());
     }
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit) = submit {
         cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+        cmd_args.push(submit.part.to_string());
+        if submit.wait {
+            cmd_args.push("--wait".to_string());
+        }
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
+    cmd_args
+}
+
+fn build_test_args(day: Day, profile: &Profile) -> Vec<String> {
+    let mut cmd_args = vec!["test".to_string(), "--bin".to_string(), day.to_string()];
+    cmd_args.extend(profile.cargo_args());
+    cmd_args
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Files that trigger a re-run when they change.
+fn watched_files(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(format!("src/bin/{day}.rs")),
+        PathBuf::from("src/lib.rs"),
+        PathBuf::from(format!("data/inputs/{day}.txt")),
+    ];
+
+    // examples can be suffixed with a part, e.g. `01-2.txt`.
+    if let Ok(entries) = fs::read_dir("data/examples") {
+        let mut examples: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.starts_with(&day.to_string()) && name.ends_with(".txt")
+                    })
+            })
+            .collect();
+        examples.sort();
+        files.extend(examples);
+    }
+
+    files
+}
+
+fn snapshot(day: Day) -> Vec<(PathBuf, Option<SystemTime>)> {
+    watched_files(day)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Run the command, forwarding its output while keeping the lines that summarize the result.
+fn run_captured(
+    profile: &Profile,
+    limits: &Limits,
+    cmd_args: &[String],
+    test: bool,
+) -> Vec<String> {
+    let mut cmd = Command::new("cargo");
+    profile.apply_env(&mut cmd);
+    limits.apply_env(&mut cmd);
+
+    let mut cmd = match cmd
+        .args(cmd_args)
+        .stdout(Stdio::piped())
         .stderr(Stdio::inherit())
         .spawn()
-        .unwrap();
+    {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            eprintln!("Failed to spawn cargo: {e}");
+            return vec![];
+        }
+    };
+
+    let mut summary = vec![];
+
+    if let Some(stdout) = cmd.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            println!("{line}");
+            // intermediate results are overwritten with a carriage return, keep the final one.
+            let line = line.rsplit('\r').next().unwrap_or_default();
+            let prefix = if test { "test " } else { "Part " };
+            if line.starts_with(prefix) {
+                summary.push(line.to_string());
+            }
+        }
+    }
+
+    let _ = cmd.wait();
+    summary
+}
 
-    cmd.wait().unwrap();
+fn watch_and_run(day: Day, profile: &Profile, limits: &Limits, cmd_args: &[String], test: bool) {
+    let mut previous: Option<Vec<String>> = None;
+    let mut state = snapshot(day);
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}");
+        println!(
+            "{ANSI_BOLD}Watching day {day}{ANSI_RESET} {ANSI_ITALIC}(cargo {}){ANSI_RESET}",
+            cmd_args.join(" ")
+        );
+        println!("------");
+
+        if let Some(previous) = &previous {
+            println!("{ANSI_ITALIC}Previous run:{ANSI_RESET}");
+            previous.iter().for_each(|line| println!("{line}"));
+            println!("------");
+        }
+
+        let summary = run_captured(profile, limits, cmd_args, test);
+        if !summary.is_empty() {
+            previous = Some(summary);
+        }
+
+        println!("------");
+        println!("{ANSI_ITALIC}Waiting for changes...{ANSI_RESET}");
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = snapshot(day);
+            if next != state {
+                // debounce editors that write files in multiple steps.
+                thread::sleep(POLL_INTERVAL);
+                state = snapshot(day);
+                break;
+            }
+        }
+    }
 }
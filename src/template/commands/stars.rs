@@ -0,0 +1,51 @@
+use std::process;
+
+use crate::template::progress::Progress;
+use crate::template::{readme_stars, Day};
+
+pub fn handle(mark: Option<(Day, u8)>, unmark: Option<(Day, u8)>) {
+    for (day, part) in mark.iter().chain(unmark.iter()) {
+        if *part != 1 && *part != 2 {
+            eprintln!("Unexpected part {part} for day {day}, expected 1 or 2.");
+            process::exit(1);
+        }
+    }
+
+    if let Some((day, part)) = mark {
+        record(day, part, true);
+    }
+
+    if let Some((day, part)) = unmark {
+        record(day, part, false);
+    }
+
+    if mark.is_none() && unmark.is_none() {
+        update_readme(&Progress::read_from_file());
+    }
+}
+
+/// Record whether a part was accepted and update the stars table in the readme.
+pub fn record(day: Day, part: u8, accepted: bool) {
+    let mut progress = Progress::read_from_file();
+    progress.set(day, part, accepted);
+
+    if let Err(e) = progress.store_file() {
+        eprintln!("Failed to store progress: {e}");
+        process::exit(1);
+    }
+
+    if accepted {
+        println!("⭐ Marked day {day}, part {part} as accepted.");
+    } else {
+        println!("Marked day {day}, part {part} as not accepted.");
+    }
+
+    update_readme(&progress);
+}
+
+fn update_readme(progress: &Progress) {
+    match readme_stars::update(progress) {
+        Ok(()) => println!("Updated readme with {} stars.", progress.count_stars()),
+        Err(_) => eprintln!("Failed to update readme stars."),
+    }
+}
@@ -1,34 +1,86 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process;
 
+use crate::template::budget::{self, Budget};
+use crate::template::export::{self, Format};
+use crate::template::limits::Limits;
+use crate::template::machine::Machine;
+use crate::template::profile::Profile;
+use crate::template::report::Report;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, fingerprint, history, readme_benchmarks, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    profile: &Profile,
+    limits: &Limits,
+    reports: &[Report],
+) {
+    let machine = Machine::detect();
+    let stored_timings = match Timings::read_from_file() {
+        Ok(timings) => timings,
+        // NOTE: storing would replace the unreadable file and lose every timing in it.
+        Err(e) if store => {
+            eprintln!("Failed to read stored timings, not benching: {e}");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to read stored timings, benching all days: {e}");
+            Timings::default()
+        }
+    };
+    let machine_timings = stored_timings.for_machine(&machine.label);
+
+    let hashes: HashMap<Day, String> = all_days()
+        .map(|day| (day, fingerprint::day_hash(day)))
+        .collect();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
             } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
+                // when the `--all` flag is not set, filter out days that are fully benched,
+                // unless their code or input changed since.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| {
+                        !machine_timings.is_day_complete(*day)
+                            || machine_timings.is_day_stale(*day, &hashes[day])
+                    })
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(&days_to_run, profile, true, limits, reports)
+        .unwrap()
+        .with_machine(&machine);
+
+    // partial timings of an interrupted day would replace complete ones.
+    timings.data.retain(|timing| !timing.interrupted);
+
+    for timing in &mut timings.data {
+        timing.source_hash = hashes.get(&timing.day).cloned();
+    }
+
+    // the budget covers the whole calendar, including days that were not benched in this run.
+    budget::print(&machine_timings.merge(&timings), &Budget::from_env());
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        if let Err(e) = history::append(&timings) {
+            eprintln!("Failed to append timings to history: {e}");
+        }
+
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(&merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -38,3 +90,14 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+pub fn print_history(day: Day) {
+    history::print_day(day);
+}
+
+pub fn export(format: Format, path: &Path) {
+    if let Err(e) = export::export(format, path) {
+        eprintln!("Failed to export timings: {e}");
+        process::exit(1);
+    }
+}
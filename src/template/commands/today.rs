@@ -0,0 +1,106 @@
+use std::{
+    io::{stdout, Write},
+    process, thread,
+    time::Duration,
+};
+
+use chrono::{Datelike, Utc};
+
+use crate::template::{
+    aoc_cli::get_year,
+    commands::{download, read, scaffold},
+    inputs, Day,
+};
+
+/// Scaffold, download and read the puzzle of the current day.
+/// With `wait`, counts down to the next unlock first, unless today's puzzle was not downloaded yet.
+pub fn handle(wait: bool) {
+    let day = if wait {
+        wait_for_unlock()
+    } else {
+        Day::today().unwrap_or_else(|| {
+            eprintln!(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day, \
+                or `today --wait` to wait for the next puzzle."
+            );
+            process::exit(1)
+        })
+    };
+
+    scaffold::handle(day, false);
+
+    if wait {
+        download::handle_with_retry(day);
+    } else {
+        download::handle(day, None, false);
+    }
+
+    read::handle(day);
+}
+
+fn wait_for_unlock() -> Day {
+    if let Some(day) = Day::today().filter(|day| !inputs::exists(*day, None)) {
+        return day;
+    }
+
+    let year = get_year().map_or_else(|| Utc::now().year(), i32::from);
+
+    let Some((day, unlock)) = Day::next_unlock(Utc::now(), year) else {
+        eprintln!("All puzzles of {year} are unlocked already.");
+        process::exit(1);
+    };
+
+    while let Some(remaining) = (unlock - Utc::now())
+        .to_std()
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+    {
+        print!(
+            "\r🎄 Day {day} unlocks in {}. ",
+            format_countdown(remaining)
+        );
+        let _ = stdout().flush();
+
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!("\r🎄 Day {day} is unlocked!{}", " ".repeat(20));
+    day
+}
+
+/// Format a duration like `2d 03:12:05`, leaving out days if there are none.
+fn format_countdown(remaining: Duration) -> String {
+    // round up, so that the countdown reaches zero when the puzzle unlocks.
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60
+    );
+
+    match secs / 86400 {
+        0 => time,
+        days => format!("{days}d {time}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::format_countdown;
+
+    #[test]
+    fn formats_countdowns() {
+        assert_eq!(format_countdown(Duration::from_secs(65)), "00:01:05");
+        assert_eq!(format_countdown(Duration::from_millis(59_200)), "00:01:00");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86400 + 3 * 3600 + 12 * 60 + 5)),
+            "2d 03:12:05"
+        );
+    }
+}
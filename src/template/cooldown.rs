@@ -0,0 +1,250 @@
+/// Tracks how long Advent of Code blocks further answers for a day.
+/// Cooldowns are parsed from submission responses and stored in `data/cooldowns.json` as unix timestamps of their expiry.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static COOLDOWNS_FILE_PATH: &str = "./data/cooldowns.json";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cooldowns {
+    /// Unix timestamp at which answers for a day are accepted again.
+    pub until: BTreeMap<Day, u64>,
+}
+
+impl Cooldowns {
+    /// Dehydrate cooldowns to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(COOLDOWNS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate cooldowns from a JSON file. If not present, returns no cooldowns.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(COOLDOWNS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Cooldowns::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Start a cooldown for a day that lasts `wait` from `now`.
+    pub fn set(&mut self, day: Day, now: u64, wait: Duration) {
+        self.until.insert(day, now + wait.as_secs());
+    }
+
+    /// Time left until answers for a day are accepted again, if any.
+    pub fn remaining(&self, day: Day, now: u64) -> Option<Duration> {
+        self.until
+            .get(&day)
+            .filter(|until| **until > now)
+            .map(|until| Duration::from_secs(until - now))
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Parse how long to wait from a submission response, either from aoc-cli or the native client.
+/// Understands both "You have 1m 30s left to wait." and "please wait 5 minutes before trying again."
+pub fn parse_wait(message: &str) -> Option<Duration> {
+    // aoc-cli output may be colored and wrapped over several lines.
+    let message = strip_ansi(message)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let message = message.to_lowercase();
+
+    if let Some(end) = message.find(" left to wait") {
+        let start = message[..end].rfind("you have ")? + "you have ".len();
+        return parse_duration(&message[start..end]);
+    }
+
+    let start = message.find("please wait ")? + "please wait ".len();
+    let end = start + message[start..].find(" before trying again")?;
+    parse_duration(&message[start..end])
+}
+
+/// Parse durations like `1m 30s`, `41s`, `one minute` or `5 minutes`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let mut secs = 0;
+    let mut tokens = s.split_whitespace();
+    let mut found = false;
+
+    while let Some(token) = tokens.next() {
+        let (value, unit) = match token.find(|c: char| !c.is_ascii_digit()) {
+            Some(0) => (parse_word(token)?, tokens.next()?),
+            Some(i) => (token[..i].parse().ok()?, &token[i..]),
+            None => (token.parse().ok()?, tokens.next()?),
+        };
+
+        secs += match unit.trim_end_matches(['.', ',', ';']) {
+            "h" | "hour" | "hours" => value * 3600,
+            "m" | "min" | "minute" | "minutes" => value * 60,
+            "s" | "sec" | "second" | "seconds" => value,
+            _ => return None,
+        };
+        found = true;
+    }
+
+    found.then(|| Duration::from_secs(secs))
+}
+
+fn parse_word(s: &str) -> Option<u64> {
+    let words = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    words.iter().position(|w| *w == s).map(|i| i as u64)
+}
+
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the escape sequence up to its final letter.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+/// Format a duration like `1m 30s`.
+pub fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}s"),
+        (m, 0) => format!("{m}m"),
+        (m, s) => format!("{m}m {s}s"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Cooldowns> for JsonValue {
+    fn from(value: &Cooldowns) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let map: HashMap<String, JsonValue> = value
+            .until
+            .iter()
+            .map(|(day, until)| (day.to_string(), JsonValue::Number(*until as f64)))
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Cooldowns {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut cooldowns = Cooldowns::default();
+
+        for (day, until) in json {
+            let day = Day::from_str(day).map_err(|_| format!("expected `{day}` to be a day."))?;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let until = until
+                .get::<f64>()
+                .map(|v| *v as u64)
+                .ok_or("expected cooldown to be a timestamp.")?;
+
+            cooldowns.until.insert(day, until);
+        }
+
+        Ok(cooldowns)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{format_wait, parse_wait, Cooldowns};
+    use crate::day;
+
+    #[test]
+    fn parses_native_responses() {
+        assert_eq!(
+            parse_wait("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 41s left to wait. [Return to Day 1]"),
+            Some(Duration::from_secs(41))
+        );
+        assert_eq!(
+            parse_wait("You gave an answer too recently. You have 4m 59s left to wait."),
+            Some(Duration::from_secs(299))
+        );
+        assert_eq!(
+            parse_wait("That's not the right answer. Please wait one minute before trying again."),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_wait("That's not the right answer; your answer is too low. Please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn parses_aoc_cli_output() {
+        let output = "\x1b[33mYou gave an answer too recently; you have to wait after\nsubmitting an answer before trying again. You have 1m\n5s left to wait.\x1b[0m\n";
+        assert_eq!(parse_wait(output), Some(Duration::from_secs(65)));
+    }
+
+    #[test]
+    fn tracks_remaining_time() {
+        let mut cooldowns = Cooldowns::default();
+        cooldowns.set(day!(1), 1000, Duration::from_secs(60));
+
+        assert_eq!(
+            cooldowns.remaining(day!(1), 1030),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(cooldowns.remaining(day!(1), 1060), None);
+        assert_eq!(cooldowns.remaining(day!(2), 1030), None);
+    }
+
+    #[test]
+    fn roundtrips_cooldowns() {
+        let mut cooldowns = Cooldowns::default();
+        cooldowns.set(day!(3), 1_733_029_200, Duration::from_secs(60));
+
+        let json = JsonValue::from(&cooldowns).stringify().unwrap();
+        assert_eq!(Cooldowns::try_from(json).unwrap(), cooldowns);
+    }
+
+    #[test]
+    fn formats_waits() {
+        assert_eq!(format_wait(Duration::from_secs(41)), "41s");
+        assert_eq!(format_wait(Duration::from_secs(300)), "5m");
+        assert_eq!(format_wait(Duration::from_secs(299)), "4m 59s");
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
@@ -55,6 +55,19 @@ impl Day {
             None
         }
     }
+
+    /// Returns the next day to unlock in the advent of `year` and the moment it unlocks,
+    /// `None` once the 25th has unlocked. Puzzles unlock at midnight server time.
+    pub fn next_unlock(now: DateTime<Utc>, year: i32) -> Option<(Self, DateTime<Utc>)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        all_days().find_map(|day| {
+            let unlock = offset
+                .with_ymd_and_hms(year, 12, u32::from(day.0), 0, 0, 0)
+                .single()?
+                .with_timezone(&Utc);
+            (unlock > now).then_some((day, unlock))
+        })
+    }
 }
 
 impl Display for Day {
@@ -187,6 +200,32 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[cfg(feature = "today")]
+    #[test]
+    fn next_unlock() {
+        use chrono::{TimeZone, Utc};
+
+        let at = |month, day, hour, min| {
+            Utc.with_ymd_and_hms(2024, month, day, hour, min, 0)
+                .unwrap()
+        };
+
+        assert_eq!(
+            Day::next_unlock(at(11, 20, 12, 0), 2024),
+            Some((Day(1), at(12, 1, 5, 0)))
+        );
+        assert_eq!(
+            Day::next_unlock(at(12, 1, 4, 59), 2024),
+            Some((Day(1), at(12, 1, 5, 0)))
+        );
+        assert_eq!(
+            Day::next_unlock(at(12, 1, 5, 0), 2024),
+            Some((Day(2), at(12, 2, 5, 0)))
+        );
+        assert_eq!(Day::next_unlock(at(12, 25, 5, 0), 2024), None);
+        assert_eq!(Day::next_unlock(at(12, 1, 0, 0), 2023), None);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
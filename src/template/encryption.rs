@@ -0,0 +1,235 @@
+/// Optional encryption at rest of the inputs in `data/inputs` and puzzles in `data/puzzles`,
+/// so they can be committed to a team repository without publishing them.
+/// The key is a random 32-byte key in hex, read from `AOC_ENCRYPTION_KEY` or from the key file in
+/// `AOC_ENCRYPTION_KEY_FILE` (default `.aoc-key`). Without a key, files are written in plain text.
+use std::{env, fmt::Display, fs, io, path::Path};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+pub const KEY_ENV: &str = "AOC_ENCRYPTION_KEY";
+pub const KEY_FILE_ENV: &str = "AOC_ENCRYPTION_KEY_FILE";
+const DEFAULT_KEY_FILE: &str = ".aoc-key";
+const KEY_LEN: usize = 32;
+
+/// Starts every encrypted file, followed by the nonce and the ciphertext.
+const MAGIC: &[u8] = b"aoc-encrypted-v1\n";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EncryptionError {
+    MissingKey,
+    /// The key is not 32 bytes in hex, e.g. because it is a passphrase.
+    InvalidKey,
+    /// The file was encrypted with another key or was changed since.
+    Decrypt,
+}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::MissingKey => write!(
+                f,
+                "the file is encrypted, set `{KEY_ENV}` or add the key to `{DEFAULT_KEY_FILE}` to use it."
+            ),
+            EncryptionError::InvalidKey => write!(
+                f,
+                "the key must be {KEY_LEN} random bytes in hex, generate one with `cargo encrypt --generate-key`."
+            ),
+            EncryptionError::Decrypt => write!(
+                f,
+                "could not decrypt the file, the key is wrong or the file is corrupted."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncryptionError {}
+
+pub struct Cipher(ChaCha20Poly1305);
+
+impl Cipher {
+    /// The cipher of a key in hex. Leading and trailing whitespace is ignored.
+    pub fn new(key: &str) -> Result<Self, EncryptionError> {
+        let key = decode_key(key.trim()).ok_or(EncryptionError::InvalidKey)?;
+        Ok(Cipher(ChaCha20Poly1305::new(Key::from_slice(&key))))
+    }
+
+    /// The cipher of the configured key, or `None` if encryption is not set up.
+    /// A key file that is set explicitly but can not be read is an error rather than silently disabling encryption,
+    /// and so is a key that is not 32 bytes in hex.
+    pub fn from_env() -> Result<Option<Self>, io::Error> {
+        let cipher = |key: &str, source: &str| {
+            Cipher::new(key)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{source}: {e}")))
+        };
+
+        if let Some(key) = env::var(KEY_ENV).ok().filter(|s| !s.trim().is_empty()) {
+            return cipher(&key, &format!("`{KEY_ENV}`")).map(Some);
+        }
+
+        let path = key_file_path();
+        let key = match fs::read_to_string(&path) {
+            Ok(key) => key,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && env::var(KEY_FILE_ENV).is_err() => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e),
+        };
+
+        if key.trim().is_empty() {
+            return Ok(None);
+        }
+        cipher(&key, &format!("\"{path}\"")).map(Some)
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .0
+            .encrypt(&nonce, plaintext)
+            .expect("could not encrypt file");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let data = data.strip_prefix(MAGIC).ok_or(EncryptionError::Decrypt)?;
+        if data.len() < NONCE_LEN {
+            return Err(EncryptionError::Decrypt);
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        self.0
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| EncryptionError::Decrypt)
+    }
+}
+
+/// The path of the key file, from `AOC_ENCRYPTION_KEY_FILE` or the default `.aoc-key`.
+pub fn key_file_path() -> String {
+    env::var(KEY_FILE_ENV).unwrap_or_else(|_| DEFAULT_KEY_FILE.into())
+}
+
+/// A new random key in hex.
+pub fn generate_key() -> String {
+    ChaCha20Poly1305::generate_key(&mut OsRng)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn decode_key(key: &str) -> Option<Vec<u8>> {
+    if key.len() != KEY_LEN * 2 || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..key.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&key[i..i + 2], 16).ok())
+        .collect()
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// Read a file to a string, decrypting it if it is encrypted. Plain text files are read as is.
+pub fn read_to_string(path: impl AsRef<Path>) -> Result<String, io::Error> {
+    let path = path.as_ref();
+    let data = fs::read(path)?;
+
+    let data = if is_encrypted(&data) {
+        let cipher = Cipher::from_env()?.ok_or(EncryptionError::MissingKey);
+        cipher
+            .and_then(|cipher| cipher.decrypt(&data))
+            .map_err(|e| io::Error::other(format!("\"{}\": {e}", path.display())))?
+    } else {
+        data
+    };
+
+    String::from_utf8(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Write a file, encrypting it if a key is configured.
+/// Without a key, an encrypted file is not replaced by a plain text copy.
+pub fn write(path: impl AsRef<Path>, contents: &str) -> Result<(), io::Error> {
+    let path = path.as_ref();
+
+    match Cipher::from_env()? {
+        Some(cipher) => fs::write(path, cipher.encrypt(contents.as_bytes())),
+        None if fs::read(path).is_ok_and(|data| is_encrypted(&data)) => Err(io::Error::other(
+            format!("\"{}\": {}", path.display(), EncryptionError::MissingKey),
+        )),
+        None => fs::write(path, contents),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{generate_key, is_encrypted, Cipher, EncryptionError};
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn roundtrips_files() {
+        let cipher = Cipher::new(&format!("{KEY}\n")).unwrap();
+        let encrypted = cipher.encrypt(b"3   4\n4   3\n");
+
+        assert!(is_encrypted(&encrypted));
+        assert!(!is_encrypted(b"3   4\n4   3\n"));
+        assert_ne!(encrypted, cipher.encrypt(b"3   4\n4   3\n"));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), b"3   4\n4   3\n");
+        assert_eq!(
+            Cipher::new(&KEY.to_uppercase())
+                .unwrap()
+                .decrypt(&encrypted)
+                .unwrap(),
+            b"3   4\n4   3\n"
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_keys_and_corrupted_files() {
+        let cipher = Cipher::new(KEY).unwrap();
+        let encrypted = cipher.encrypt(b"3   4\n");
+
+        assert_eq!(
+            Cipher::new(&generate_key()).unwrap().decrypt(&encrypted),
+            Err(EncryptionError::Decrypt)
+        );
+
+        let mut corrupted = encrypted.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert_eq!(cipher.decrypt(&corrupted), Err(EncryptionError::Decrypt));
+        assert_eq!(
+            cipher.decrypt(&encrypted[..20]),
+            Err(EncryptionError::Decrypt)
+        );
+    }
+
+    #[test]
+    fn requires_random_keys() {
+        let key = generate_key();
+        assert_eq!(key.len(), 64);
+        assert_ne!(key, generate_key());
+        assert!(Cipher::new(&key).is_ok());
+
+        for invalid in [
+            "correct horse battery staple",
+            &KEY[..62],
+            &format!("{KEY}00"),
+            &format!("+{}", &KEY[1..]),
+            &format!("{}zz", &KEY[..62]),
+        ] {
+            assert_eq!(
+                Cipher::new(invalid).err(),
+                Some(EncryptionError::InvalidKey)
+            );
+        }
+    }
+}
@@ -0,0 +1,394 @@
+/// Module that exports stored timings and their history for analysis in other tools.
+/// Every stored timing and every history record becomes one row.
+use std::{error::Error, fmt::Display, fs, io, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    history::{self, HistoryRecord},
+    report::escape_xml,
+    timings::{Timing, Timings},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    JsonLines,
+    Html,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Csv => f.write_str("CSV"),
+            Format::JsonLines => f.write_str("JSON Lines"),
+            Format::Html => f.write_str("HTML"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "jsonl" => Ok(Format::JsonLines),
+            "html" => Ok(Format::Html),
+            _ => Err(FormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Format`].
+#[derive(Debug)]
+pub struct FormatFromStrError;
+
+impl Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an export format, one of `csv`, `jsonl` or `html`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A stored timing or a history record, flattened to a single row.
+struct Row<'a> {
+    source: &'static str,
+    commit: Option<&'a str>,
+    timestamp: Option<u64>,
+    timing: &'a Timing,
+}
+
+const COLUMNS: [&str; 19] = [
+    "source",
+    "machine",
+    "day",
+    "profile",
+    "part_1",
+    "part_2",
+    "part_1_nanos",
+    "part_2_nanos",
+    "total_nanos",
+    "part_1_samples",
+    "part_2_samples",
+    "peak_memory_kb",
+    "verified",
+    "source_hash",
+    "inputs",
+    "part_1_max_nanos",
+    "part_2_max_nanos",
+    "commit",
+    "timestamp",
+];
+
+impl Row<'_> {
+    /// The row's values in the order of [`COLUMNS`]. Missing values are empty.
+    fn values(&self) -> [String; 19] {
+        let t = self.timing;
+        let string = |x: Option<&str>| x.map(str::to_string).unwrap_or_default();
+        let number = |x: Option<f64>| x.map(|x| x.to_string()).unwrap_or_default();
+        let count = |x: Option<u64>| x.map(|x| x.to_string()).unwrap_or_default();
+
+        [
+            self.source.to_string(),
+            string(t.machine.as_deref()),
+            t.day.to_string(),
+            string(t.profile.as_deref()),
+            string(t.part_1.as_deref()),
+            string(t.part_2.as_deref()),
+            number(t.part_1_nanos),
+            number(t.part_2_nanos),
+            t.total_nanos.to_string(),
+            count(t.part_1_samples),
+            count(t.part_2_samples),
+            count(t.peak_memory_kb),
+            t.verified.map(|x| x.to_string()).unwrap_or_default(),
+            string(t.source_hash.as_deref()),
+            count(t.inputs),
+            number(t.part_1_max_nanos),
+            number(t.part_2_max_nanos),
+            string(self.commit),
+            count(self.timestamp),
+        ]
+    }
+}
+
+fn collect_rows<'a>(timings: &'a Timings, history: &'a [HistoryRecord]) -> Vec<Row<'a>> {
+    let current = timings.data.iter().map(|timing| Row {
+        source: "timings",
+        commit: None,
+        timestamp: None,
+        timing,
+    });
+
+    let recorded = history.iter().map(|record| Row {
+        source: "history",
+        commit: record.commit.as_deref(),
+        timestamp: Some(record.timestamp),
+        timing: &record.timing,
+    });
+
+    current.chain(recorded).collect()
+}
+
+/// Write the timings and history to `path` in the given format.
+pub fn write(
+    format: Format,
+    path: &Path,
+    timings: &Timings,
+    history: &[HistoryRecord],
+) -> Result<(), io::Error> {
+    let rows = collect_rows(timings, history);
+    let contents = match format {
+        Format::Csv => format_csv(&rows),
+        Format::JsonLines => format_json_lines(&rows)?,
+        Format::Html => format_html(&rows),
+    };
+    fs::write(path, contents)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn format_csv(rows: &[Row]) -> String {
+    let mut lines = vec![COLUMNS.join(",")];
+
+    for row in rows {
+        let values: Vec<String> = row.values().iter().map(|v| escape_csv(v)).collect();
+        lines.push(values.join(","));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn format_json_lines(rows: &[Row]) -> Result<String, io::Error> {
+    let mut lines = vec![];
+
+    for row in rows {
+        let json = match row.timestamp {
+            Some(timestamp) => JsonValue::from(&HistoryRecord {
+                commit: row.commit.map(str::to_string),
+                timestamp,
+                timing: row.timing.clone(),
+            }),
+            None => JsonValue::from(row.timing),
+        };
+
+        let JsonValue::Object(mut map) = json else {
+            unreachable!("timings always serialize to an object.");
+        };
+        map.insert("source".into(), JsonValue::String(row.source.into()));
+
+        lines.push(
+            JsonValue::Object(map)
+                .stringify()
+                .map_err(io::Error::other)?,
+        );
+    }
+
+    lines.push(String::new());
+    Ok(lines.join("\n"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+static HTML_STYLE: &str = "body { font-family: sans-serif; margin: 2rem; }
+table { border-collapse: collapse; margin-bottom: 2rem; font-size: 0.9rem; }
+th, td { border: 1px solid #ddd; padding: 0.3rem 0.6rem; text-align: right; }
+th { background: #f4f4f4; cursor: pointer; user-select: none; }
+th[aria-sort=ascending]::after { content: \" ▲\"; }
+th[aria-sort=descending]::after { content: \" ▼\"; }";
+
+/// Sorts a table by the clicked column. Cells carry a `data-value` attribute if they sort numerically.
+static HTML_SCRIPT: &str = "document.querySelectorAll('th').forEach((th) => {
+  th.addEventListener('click', () => {
+    const table = th.closest('table');
+    const index = Array.from(th.parentNode.children).indexOf(th);
+    const ascending = th.getAttribute('aria-sort') !== 'ascending';
+    table.querySelectorAll('th').forEach((other) => other.removeAttribute('aria-sort'));
+    th.setAttribute('aria-sort', ascending ? 'ascending' : 'descending');
+    const value = (row) => {
+      const cell = row.children[index];
+      return cell.dataset.value !== undefined ? parseFloat(cell.dataset.value) : cell.textContent;
+    };
+    const body = table.tBodies[0];
+    Array.from(body.rows)
+      .sort((a, b) => {
+        const [x, y] = [value(a), value(b)];
+        const order = typeof x === 'number' && typeof y === 'number' ? x - y : String(x).localeCompare(String(y));
+        return ascending ? order : -order;
+      })
+      .forEach((row) => body.appendChild(row));
+  });
+});";
+
+fn format_html(rows: &[Row]) -> String {
+    let mut html = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".into(),
+        "<head>".into(),
+        "<meta charset=\"utf-8\">".into(),
+        "<title>Benchmarks</title>".into(),
+        format!("<style>\n{HTML_STYLE}\n</style>"),
+        "</head>".into(),
+        "<body>".into(),
+    ];
+
+    for (source, title) in [("timings", "Timings"), ("history", "History")] {
+        let rows: Vec<&Row> = rows.iter().filter(|r| r.source == source).collect();
+        if rows.is_empty() {
+            continue;
+        }
+
+        html.push(format!("<h2>{title}</h2>"));
+        html.push("<table>".into());
+        html.push("<thead>".into());
+        html.push(format!(
+            "<tr>{}</tr>",
+            COLUMNS[1..]
+                .iter()
+                .map(|c| format!("<th>{c}</th>"))
+                .collect::<String>()
+        ));
+        html.push("</thead>".into());
+        html.push("<tbody>".into());
+
+        for row in rows {
+            let cells: String = row.values()[1..]
+                .iter()
+                .map(|v| match v.parse::<f64>() {
+                    Ok(n) => format!("<td data-value=\"{n}\">{}</td>", escape_xml(v)),
+                    Err(_) => format!("<td>{}</td>", escape_xml(v)),
+                })
+                .collect();
+            html.push(format!("<tr>{cells}</tr>"));
+        }
+
+        html.push("</tbody>".into());
+        html.push("</table>".into());
+    }
+
+    html.push(format!("<script>\n{HTML_SCRIPT}\n</script>"));
+    html.push("</body>".into());
+    html.push("</html>".into());
+    html.push(String::new());
+    html.join("\n")
+}
+
+/// Read the stored timings and history and export them, printing the outcome.
+pub fn export(format: Format, path: &Path) -> Result<(), io::Error> {
+    let timings = Timings::read_from_file().map_err(io::Error::other)?;
+    let history = history::read_from_file();
+
+    write(format, path, &timings, &history)?;
+
+    println!(
+        "Exported {} timings and {} history records as {format} to \"{}\".",
+        timings.data.len(),
+        history.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{collect_rows, format_csv, format_html, format_json_lines, Format};
+    use crate::{
+        day,
+        template::{
+            history::HistoryRecord,
+            timings::{mock_timing, Timing, Timings},
+        },
+    };
+
+    fn get_mock_data() -> (Timings, Vec<HistoryRecord>) {
+        let timing = Timing {
+            profile: Some("bench".into()),
+            machine: Some("ci, linux".into()),
+            part_1_samples: Some(1000),
+            peak_memory_kb: Some(2048),
+            verified: Some(true),
+            ..mock_timing(day!(1), Some("1ms"), None)
+        };
+        let timings = Timings {
+            data: vec![timing.clone()],
+            machines: vec![],
+        };
+        let history = vec![HistoryRecord {
+            commit: Some("abc1234".into()),
+            timestamp: 1_733_029_200,
+            timing,
+        }];
+        (timings, history)
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(Format::from_str("csv").unwrap(), Format::Csv);
+        assert_eq!(Format::from_str("jsonl").unwrap(), Format::JsonLines);
+        assert_eq!(Format::from_str("html").unwrap(), Format::Html);
+        assert!(Format::from_str("xlsx").is_err());
+    }
+
+    #[test]
+    fn formats_csv() {
+        let (timings, history) = get_mock_data();
+        let csv = format_csv(&collect_rows(&timings, &history));
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("source,machine,day,profile,"));
+        assert_eq!(
+            lines[1],
+            "timings,\"ci, linux\",01,bench,1ms,,1000000,,1000000,1000,,2048,true,,,,,,"
+        );
+        assert_eq!(
+            lines[2],
+            "history,\"ci, linux\",01,bench,1ms,,1000000,,1000000,1000,,2048,true,,,,,abc1234,1733029200"
+        );
+    }
+
+    #[test]
+    fn formats_json_lines() {
+        let (timings, history) = get_mock_data();
+        let jsonl = format_json_lines(&collect_rows(&timings, &history)).unwrap();
+        let lines: Vec<&str> = jsonl.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains(r#""source":"timings""#));
+        assert!(!lines[0].contains("commit"));
+        assert!(lines[1].contains(r#""source":"history""#));
+        assert!(lines[1].contains(r#""commit":"abc1234""#));
+    }
+
+    #[test]
+    fn formats_html() {
+        let (timings, history) = get_mock_data();
+        let html = format_html(&collect_rows(&timings, &history));
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h2>Timings</h2>"));
+        assert!(html.contains("<h2>History</h2>"));
+        assert!(html.contains("<td data-value=\"1000000\">1000000</td>"));
+        assert!(html.contains("<td>ci, linux</td>"));
+        assert_eq!(html.matches("<tr>").count(), 4);
+
+        let html = format_html(&collect_rows(&timings, &[]));
+        assert!(!html.contains("<h2>History</h2>"));
+    }
+}
@@ -0,0 +1,104 @@
+/// Fingerprints of the files that a day's timings depend on.
+/// A timing is stale once the fingerprint of its solution, the shared library code or its inputs changed.
+use std::{fs, path::PathBuf};
+
+use crate::template::{encryption, input_variants, Day};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// 64-bit FNV-1a hash, see: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// The solution, the library and the default input, followed by the day's other input variants.
+fn get_paths(day: Day) -> Vec<PathBuf> {
+    let default = PathBuf::from(format!("./data/inputs/{day}.txt"));
+
+    let mut paths = vec![
+        PathBuf::from(format!("./src/bin/{day}.rs")),
+        "./src/lib.rs".into(),
+        default.clone(),
+    ];
+
+    paths.extend(
+        input_variants(day)
+            .into_iter()
+            .filter(|path| path.file_name() != default.file_name()),
+    );
+
+    paths
+}
+
+/// Hash the contents of `files`. Every file is prefixed with its length, so moving bytes between files changes the hash.
+fn hash_contents(files: &[Vec<u8>]) -> String {
+    let mut hasher = Fnv1a::new();
+    for contents in files {
+        hasher.write(&(contents.len() as u64).to_le_bytes());
+        hasher.write(contents);
+    }
+    format!("{:016x}", hasher.0)
+}
+
+/// Checksum of a single file, e.g. to notice when a downloaded input was changed.
+pub fn checksum(contents: &[u8]) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write(contents);
+    format!("{:016x}", hasher.0)
+}
+
+/// Fingerprint of a day's solution, the library and the day's inputs. Missing files hash as empty.
+/// Encrypted inputs are hashed by their decrypted contents, so encrypting or decrypting them keeps timings fresh.
+pub fn day_hash(day: Day) -> String {
+    let files: Vec<Vec<u8>> = get_paths(day)
+        .iter()
+        .map(|path| {
+            encryption::read_to_string(path)
+                .map(String::into_bytes)
+                .or_else(|_| fs::read(path))
+                .unwrap_or_default()
+        })
+        .collect();
+    hash_contents(&files)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{hash_contents, Fnv1a};
+
+    #[test]
+    fn hashes_fnv1a() {
+        // reference values from the FNV test suite.
+        let mut hasher = Fnv1a::new();
+        assert_eq!(hasher.0, 0xcbf2_9ce4_8422_2325);
+        hasher.write(b"a");
+        assert_eq!(hasher.0, 0xaf63_dc4c_8601_ec8c);
+
+        let mut hasher = Fnv1a::new();
+        hasher.write(b"foobar");
+        assert_eq!(hasher.0, 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn hashes_file_boundaries() {
+        let a = hash_contents(&[b"ab".to_vec(), b"c".to_vec()]);
+        let b = hash_contents(&[b"a".to_vec(), b"bc".to_vec()]);
+        assert_ne!(a, b);
+        assert_eq!(a.len(), 16);
+        assert_eq!(a, hash_contents(&[b"ab".to_vec(), b"c".to_vec()]));
+    }
+}
@@ -0,0 +1,321 @@
+/// Append-only log of stored benchmark timings.
+/// Every `cargo time --store` appends one record per benched day, tagged with the git commit and a timestamp.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    table::print_table,
+    timings::{Timing, Timings},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// A single timing, as recorded at a point in time.
+#[derive(Clone, Debug)]
+pub struct HistoryRecord {
+    pub commit: Option<String>,
+    pub timestamp: u64,
+    pub timing: Timing,
+}
+
+/// Append the timings to the history file, tagged with the current commit and time.
+pub fn append(timings: &Timings) -> Result<(), Error> {
+    let commit = current_commit();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+
+    for timing in &timings.data {
+        let record = HistoryRecord {
+            commit: commit.clone(),
+            timestamp,
+            timing: timing.clone(),
+        };
+        writeln!(
+            file,
+            "{}",
+            JsonValue::from(&record).stringify().map_err(Error::other)?
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Read all records from the history file. Lines that can not be parsed are skipped.
+pub fn read_from_file() -> Vec<HistoryRecord> {
+    fs::read_to_string(HISTORY_FILE_PATH)
+        .map(|s| parse_history(&s))
+        .unwrap_or_default()
+}
+
+fn parse_history(s: &str) -> Vec<HistoryRecord> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let json = JsonValue::from_str(line).ok()?;
+            HistoryRecord::try_from(&json)
+                .map_err(|e| eprintln!("Skipping invalid history record: {e}"))
+                .ok()
+        })
+        .collect()
+}
+
+/// The abbreviated hash of `HEAD`, suffixed with `-dirty` if the working tree has changes.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Print how the timings of a day changed over the recorded history.
+pub fn print_day(day: Day) {
+    let records: Vec<HistoryRecord> = read_from_file()
+        .into_iter()
+        .filter(|r| r.timing.day == day)
+        .collect();
+
+    if records.is_empty() {
+        println!("No history recorded for day {day}. Run `cargo time {day} --store` to start one.");
+        return;
+    }
+
+    println!("{ANSI_BOLD}Day {day} history{ANSI_RESET}");
+    println!();
+
+    print_table(&construct_rows(&records));
+}
+
+/// One row per record. The change is relative to the previous record of the same machine and profile,
+/// since timings of different machines or profiles are not comparable.
+fn construct_rows(records: &[HistoryRecord]) -> Vec<[String; 7]> {
+    let mut rows: Vec<[String; 7]> = vec![[
+        "Commit".into(),
+        "Date (UTC)".into(),
+        "Machine".into(),
+        "Profile".into(),
+        "Part 1".into(),
+        "Part 2".into(),
+        "Total".into(),
+    ]];
+
+    let mut previous_totals: HashMap<(Option<&str>, Option<&str>), f64> = HashMap::new();
+
+    for record in records {
+        let total = record.timing.total_nanos;
+        let key = (
+            record.timing.machine.as_deref(),
+            record.timing.profile.as_deref(),
+        );
+        let change = previous_totals
+            .insert(key, total)
+            .filter(|p| *p > 0.0)
+            .map(|p| format!(" ({:+.1}%)", (total - p) / p * 100.0))
+            .unwrap_or_default();
+
+        rows.push([
+            record.commit.clone().unwrap_or_else(|| "-".into()),
+            format_timestamp(record.timestamp),
+            record.timing.machine.clone().unwrap_or_else(|| "-".into()),
+            record.timing.profile.clone().unwrap_or_else(|| "-".into()),
+            record.timing.part_1.clone().unwrap_or_else(|| "-".into()),
+            record.timing.part_2.clone().unwrap_or_else(|| "-".into()),
+            format!("{:.2}ms{change}", total / 1_000_000_f64),
+        ]);
+    }
+
+    rows
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD HH:MM` in UTC.
+fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    #[allow(clippy::cast_possible_wrap)]
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryRecord> for JsonValue {
+    fn from(value: &HistoryRecord) -> Self {
+        let JsonValue::Object(mut map) = JsonValue::from(&value.timing) else {
+            unreachable!("timings always serialize to an object.");
+        };
+
+        map.insert(
+            "commit".into(),
+            match value.commit.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let timing = Timing::try_from(value)?;
+
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history record to be a JSON object.")?;
+
+        let commit = match json.get("commit") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected record.commit to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected record.timestamp to be a number.")?;
+
+        Ok(HistoryRecord {
+            commit,
+            timestamp,
+            timing,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{construct_rows, format_timestamp, parse_history, HistoryRecord};
+    use crate::{
+        day,
+        template::timings::{mock_timing, Timing},
+    };
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_029_200), "2024-12-01 05:00");
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let record = HistoryRecord {
+            commit: Some("abc1234".into()),
+            timestamp: 1_733_029_200,
+            timing: Timing {
+                profile: Some("bench".into()),
+                ..mock_timing(day!(1), Some("1ms"), None)
+            },
+        };
+
+        let line = JsonValue::from(&record).stringify().unwrap();
+        let parsed = parse_history(&format!("{line}\n\n{line}\n"));
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].commit, Some("abc1234".into()));
+        assert_eq!(parsed[0].timestamp, 1_733_029_200);
+        assert_eq!(parsed[0].timing.day, day!(1));
+        assert_eq!(parsed[0].timing.profile, Some("bench".into()));
+    }
+
+    #[test]
+    fn skips_invalid_records() {
+        let parsed = parse_history("{}\nnot json\n");
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn compares_records_of_the_same_machine_and_profile() {
+        let record = |machine: &str, profile: &str, total: &str| HistoryRecord {
+            commit: None,
+            timestamp: 0,
+            timing: Timing {
+                machine: Some(machine.into()),
+                profile: Some(profile.into()),
+                ..mock_timing(day!(1), Some(total), None)
+            },
+        };
+
+        let rows = construct_rows(&[
+            record("laptop", "bench", "2ms"),
+            record("ci", "bench", "8ms"),
+            record("laptop", "release", "4ms"),
+            record("laptop", "bench", "1ms"),
+            record("ci", "bench", "10ms"),
+        ]);
+
+        let totals: Vec<&str> = rows[1..].iter().map(|row| row[6].as_str()).collect();
+        assert_eq!(
+            totals,
+            [
+                "2.00ms",
+                "8.00ms",
+                "4.00ms",
+                "1.00ms (-50.0%)",
+                "10.00ms (+25.0%)"
+            ]
+        );
+    }
+}
@@ -0,0 +1,243 @@
+/// Guards the puzzle inputs in `data/inputs` against being overwritten or replaced by error pages.
+/// The checksum of every downloaded input is taken before it is encrypted and stored in `data/input_checksums.json` to notice later changes.
+/// Inputs of named accounts are stored next to the default input, e.g. `01@alice.txt`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{encryption, fingerprint::checksum, Day};
+
+static CHECKSUMS_FILE_PATH: &str = "./data/input_checksums.json";
+
+/// Why a downloaded payload is not a puzzle input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvalidInput {
+    Empty,
+    /// The session cookie was not sent or not accepted.
+    LoggedOut,
+    /// The puzzle has not been released yet.
+    NotUnlocked,
+    NotFound,
+    /// Any other web page, e.g. a login or error page.
+    Html,
+}
+
+impl Display for InvalidInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidInput::Empty => write!(f, "the downloaded input is empty."),
+            InvalidInput::LoggedOut => {
+                write!(
+                    f,
+                    "adventofcode.com asked to log in, check the session cookie."
+                )
+            }
+            InvalidInput::NotUnlocked => write!(f, "the puzzle is not unlocked yet."),
+            InvalidInput::NotFound => write!(f, "the puzzle input was not found."),
+            InvalidInput::Html => write!(f, "received a web page instead of a puzzle input."),
+        }
+    }
+}
+
+/// Check that a downloaded payload is a puzzle input rather than one of the messages adventofcode.com sends instead.
+pub fn validate(input: &str) -> Result<(), InvalidInput> {
+    let trimmed = input.trim_start();
+
+    if trimmed.is_empty() {
+        Err(InvalidInput::Empty)
+    } else if input.contains("Puzzle inputs differ by user") {
+        Err(InvalidInput::LoggedOut)
+    } else if input.contains("before it unlocks") {
+        Err(InvalidInput::NotUnlocked)
+    } else if trimmed.starts_with("404 Not Found") {
+        Err(InvalidInput::NotFound)
+    } else if ["<!doctype", "<html"].iter().any(|tag| {
+        trimmed
+            .get(..tag.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(tag))
+    }) {
+        Err(InvalidInput::Html)
+    } else {
+        Ok(())
+    }
+}
+
+/// Whether a day already has a non-empty input file.
+pub fn exists(day: Day, account: Option<&str>) -> bool {
+    encryption::read_to_string(get_input_path(day, account)).is_ok_and(|s| !s.trim().is_empty())
+}
+
+/// Write a validated input, encrypted if a key is configured, and record its checksum.
+pub fn save(day: Day, account: Option<&str>, input: &str) -> Result<(), Error> {
+    encryption::write(get_input_path(day, account), input)?;
+
+    let mut checksums = Checksums::read_from_file();
+    checksums
+        .by_input
+        .insert(input_name(day, account), checksum(input.as_bytes()));
+    checksums.store_file()
+}
+
+/// Whether the input of a day no longer matches the checksum recorded when it was downloaded.
+pub fn is_modified(day: Day, account: Option<&str>) -> bool {
+    match (
+        Checksums::read_from_file()
+            .by_input
+            .get(&input_name(day, account)),
+        encryption::read_to_string(get_input_path(day, account)),
+    ) {
+        (Some(expected), Ok(contents)) => *expected != checksum(contents.as_bytes()),
+        _ => false,
+    }
+}
+
+/// Account names end up in file names and environment variables, so only letters, digits, `-` and `_` are allowed.
+pub fn is_valid_account(account: &str) -> bool {
+    !account.is_empty()
+        && account
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The file name of an input without extension, e.g. `01` or `01@alice`.
+pub fn input_name(day: Day, account: Option<&str>) -> String {
+    match account {
+        Some(account) => format!("{day}@{account}"),
+        None => day.to_string(),
+    }
+}
+
+pub fn get_input_path(day: Day, account: Option<&str>) -> String {
+    format!("data/inputs/{}.txt", input_name(day, account))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Checksums {
+    /// Checksums keyed by input name, e.g. `01` or `01@alice`.
+    pub by_input: BTreeMap<String, String>,
+}
+
+impl Checksums {
+    /// Dehydrate checksums to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(CHECKSUMS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate checksums from a JSON file. If not present, returns no checksums.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(CHECKSUMS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Checksums::try_from)
+            .unwrap_or_default()
+    }
+}
+
+impl From<&Checksums> for JsonValue {
+    fn from(value: &Checksums) -> Self {
+        let map: HashMap<String, JsonValue> = value
+            .by_input
+            .iter()
+            .map(|(name, checksum)| (name.clone(), JsonValue::String(checksum.clone())))
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Checksums {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut checksums = Checksums::default();
+
+        for (name, checksum) in json {
+            let checksum = checksum
+                .get::<String>()
+                .ok_or("expected checksum to be a string.")?;
+            checksums.by_input.insert(name.clone(), checksum.clone());
+        }
+
+        Ok(checksums)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{get_input_path, is_valid_account, validate, Checksums, InvalidInput};
+    use crate::day;
+
+    #[test]
+    fn accepts_puzzle_inputs() {
+        assert_eq!(validate("3   4\n4   3\n"), Ok(()));
+        assert_eq!(validate("<>^v\n"), Ok(()));
+        assert_eq!(validate("#.#\n"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_bad_payloads() {
+        assert_eq!(validate(""), Err(InvalidInput::Empty));
+        assert_eq!(validate("\n \n"), Err(InvalidInput::Empty));
+        assert_eq!(
+            validate("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            Err(InvalidInput::LoggedOut)
+        );
+        assert_eq!(
+            validate("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time."),
+            Err(InvalidInput::NotUnlocked)
+        );
+        assert_eq!(validate("404 Not Found\n"), Err(InvalidInput::NotFound));
+        assert_eq!(
+            validate("<!DOCTYPE html>\n<html lang=\"en-us\">"),
+            Err(InvalidInput::Html)
+        );
+        assert_eq!(
+            validate("<html><body></body></html>"),
+            Err(InvalidInput::Html)
+        );
+    }
+
+    #[test]
+    fn roundtrips_checksums() {
+        let mut checksums = Checksums::default();
+        checksums
+            .by_input
+            .insert("01".into(), "af63dc4c8601ec8c".into());
+        checksums
+            .by_input
+            .insert("01@alice".into(), "08328707b4eb6e38".into());
+
+        let json = JsonValue::from(&checksums).stringify().unwrap();
+        assert_eq!(Checksums::try_from(json).unwrap(), checksums);
+    }
+
+    #[test]
+    fn names_account_inputs() {
+        assert_eq!(get_input_path(day!(1), None), "data/inputs/01.txt");
+        assert_eq!(
+            get_input_path(day!(1), Some("alice")),
+            "data/inputs/01@alice.txt"
+        );
+        assert!(is_valid_account("bob_2-work"));
+        assert!(!is_valid_account("../bob"));
+        assert!(!is_valid_account(""));
+    }
+}
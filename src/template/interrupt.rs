@@ -0,0 +1,36 @@
+/// Tracks Ctrl-C presses so that long-running benchmarks can stop gracefully.
+/// The first Ctrl-C is recorded and acted upon by the caller, a second one exits immediately.
+use std::{
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static INSTALL: Once = Once::new();
+
+/// Exit code of a process that was terminated by SIGINT.
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Install the Ctrl-C handler for the current process. Can be called multiple times.
+pub fn install_handler(message: &'static str) {
+    INSTALL.call_once(|| {
+        let res = ctrlc::set_handler(move || {
+            if INTERRUPTED.swap(true, Ordering::SeqCst) {
+                process::exit(INTERRUPTED_EXIT_CODE);
+            }
+            eprintln!("\n{message} Press Ctrl-C again to abort.");
+        });
+
+        if let Err(e) = res {
+            eprintln!("Failed to install Ctrl-C handler: {e}");
+        }
+    });
+}
+
+/// Whether Ctrl-C was pressed since the handler was installed.
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
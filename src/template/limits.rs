@@ -0,0 +1,265 @@
+/// Resource limits (address space, CPU seconds) for solution binaries.
+///
+/// Limits are passed to the child process via environment variables and applied with `setrlimit`
+/// by the solution binary itself on startup, so that compiling the solution via cargo is not limited.
+use std::{collections::HashMap, env, fs, process::Command, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static LIMITS_FILE_PATH: &str = "./data/limits.json";
+
+const MEMORY_ENV: &str = "AOC_LIMIT_MEMORY_MB";
+const CPU_ENV: &str = "AOC_LIMIT_CPU_SECS";
+
+/// Printed to stderr by a solution binary that exceeded its CPU time limit.
+const CPU_LIMIT_MESSAGE: &str = "Limit exceeded: CPU time";
+/// Printed to stderr by the Rust runtime when an allocation fails.
+const ALLOCATION_FAILED_MESSAGE: &str = "memory allocation of";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub memory_mb: Option<u64>,
+    pub cpu_secs: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitKind {
+    Memory,
+    Cpu,
+}
+
+impl Limits {
+    pub fn is_empty(&self) -> bool {
+        self.memory_mb.is_none() && self.cpu_secs.is_none()
+    }
+
+    /// Resolve the limits for a day: per-day values from `data/limits.json` apply where `self` sets no limit.
+    pub fn for_day(&self, day: Day) -> Self {
+        let file_limits = fs::read_to_string(LIMITS_FILE_PATH)
+            .ok()
+            .and_then(|s| parse_limits_file(&s, day).ok())
+            .unwrap_or_default();
+
+        self.or(file_limits)
+    }
+
+    /// Fill the limits that `self` does not set from `defaults`.
+    fn or(&self, defaults: Self) -> Self {
+        Self {
+            memory_mb: self.memory_mb.or(defaults.memory_mb),
+            cpu_secs: self.cpu_secs.or(defaults.cpu_secs),
+        }
+    }
+
+    /// Pass the limits to a child command.
+    pub fn apply_env(&self, cmd: &mut Command) {
+        if let Some(memory_mb) = self.memory_mb {
+            cmd.env(MEMORY_ENV, memory_mb.to_string());
+        }
+        if let Some(cpu_secs) = self.cpu_secs {
+            cmd.env(CPU_ENV, cpu_secs.to_string());
+        }
+    }
+
+    fn from_env() -> Self {
+        let read = |key: &str| env::var(key).ok().and_then(|v| v.parse().ok());
+        Self {
+            memory_mb: read(MEMORY_ENV),
+            cpu_secs: read(CPU_ENV),
+        }
+    }
+
+    /// Detect whether a failed solution run was caused by hitting a limit.
+    pub fn exceeded(&self, stderr: &[String]) -> Option<LimitKind> {
+        if self.cpu_secs.is_some() && stderr.iter().any(|l| l.contains(CPU_LIMIT_MESSAGE)) {
+            Some(LimitKind::Cpu)
+        } else if self.memory_mb.is_some()
+            && stderr
+                .iter()
+                .any(|l| l.contains(ALLOCATION_FAILED_MESSAGE) && l.ends_with("failed"))
+        {
+            Some(LimitKind::Memory)
+        } else {
+            None
+        }
+    }
+
+    pub fn describe(&self, kind: LimitKind) -> String {
+        match kind {
+            LimitKind::Memory => format!(
+                "memory limit of {}MB exceeded.",
+                self.memory_mb.unwrap_or_default()
+            ),
+            LimitKind::Cpu => format!(
+                "CPU time limit of {}s exceeded.",
+                self.cpu_secs.unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// Apply limits passed by a parent `solve`, `all` or `time` command to the current process.
+/// Called by the `solution!` macro before any solution code runs.
+pub fn apply_from_env() {
+    let limits = Limits::from_env();
+    if !limits.is_empty() {
+        #[cfg(unix)]
+        rlimit::apply(&limits);
+        #[cfg(not(unix))]
+        eprintln!("Resource limits are only supported on unix platforms, ignoring them.");
+    }
+}
+
+#[cfg(unix)]
+mod rlimit {
+    use super::Limits;
+
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    type Resource = libc::__rlimit_resource_t;
+    #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+    type Resource = libc::c_int;
+
+    const LIMIT_EXIT_CODE: i32 = 137;
+
+    extern "C" fn on_cpu_limit(_: libc::c_int) {
+        // NOTE: allocating is not allowed in a signal handler. Keep in sync with `CPU_LIMIT_MESSAGE`.
+        static MESSAGE: &[u8] = b"\nLimit exceeded: CPU time\n";
+        // SAFETY: `write` and `_exit` are async-signal-safe.
+        unsafe {
+            libc::write(2, MESSAGE.as_ptr().cast(), MESSAGE.len());
+            libc::_exit(LIMIT_EXIT_CODE);
+        }
+    }
+
+    fn set(resource: Resource, soft: u64, hard: u64) {
+        let limit = libc::rlimit {
+            rlim_cur: soft,
+            rlim_max: hard,
+        };
+        // SAFETY: `limit` is a valid, initialized `rlimit` struct.
+        if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+            eprintln!(
+                "Failed to apply resource limit: {}",
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+
+    pub fn apply(limits: &Limits) {
+        if let Some(memory_mb) = limits.memory_mb {
+            let bytes = memory_mb.saturating_mul(1024 * 1024);
+            set(libc::RLIMIT_AS, bytes, bytes);
+        }
+
+        if let Some(cpu_secs) = limits.cpu_secs {
+            // SIGXCPU is sent when the soft limit is hit, the hard limit kills the process.
+            // SAFETY: the handler only calls async-signal-safe functions.
+            unsafe {
+                libc::signal(
+                    libc::SIGXCPU,
+                    on_cpu_limit as extern "C" fn(libc::c_int) as libc::sighandler_t,
+                );
+            }
+            set(libc::RLIMIT_CPU, cpu_secs, cpu_secs.saturating_add(1));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_limits_file(s: &str, day: Day) -> Result<Limits, String> {
+    let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+    let Some(entry) = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?
+        .get(&day.to_string())
+    else {
+        return Ok(Limits::default());
+    };
+
+    let entry = entry
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected limits to be an object.")?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let read = |key: &str| {
+        entry
+            .get(key)
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+    };
+
+    Ok(Limits {
+        memory_mb: read("memory_mb"),
+        cpu_secs: read("cpu_secs"),
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_limits_file, LimitKind, Limits};
+    use crate::day;
+
+    #[test]
+    fn parses_limits_file() {
+        let json = r#"{ "06": { "memory_mb": 512, "cpu_secs": 10 }, "07": { "cpu_secs": 5 } }"#;
+
+        let limits = parse_limits_file(json, day!(6)).unwrap();
+        assert_eq!(limits.memory_mb, Some(512));
+        assert_eq!(limits.cpu_secs, Some(10));
+
+        let limits = parse_limits_file(json, day!(7)).unwrap();
+        assert_eq!(limits.memory_mb, None);
+        assert_eq!(limits.cpu_secs, Some(5));
+
+        let limits = parse_limits_file(json, day!(8)).unwrap();
+        assert!(limits.is_empty());
+    }
+
+    #[test]
+    fn prefers_command_line_limits() {
+        let file_limits = Limits {
+            memory_mb: Some(512),
+            cpu_secs: Some(10),
+        };
+        let limits = Limits {
+            memory_mb: None,
+            cpu_secs: Some(2),
+        };
+
+        assert_eq!(
+            limits.or(file_limits),
+            Limits {
+                memory_mb: Some(512),
+                cpu_secs: Some(2),
+            }
+        );
+        assert_eq!(Limits::default().or(file_limits), file_limits);
+    }
+
+    #[test]
+    fn detects_exceeded_limits() {
+        let limits = Limits {
+            memory_mb: Some(64),
+            cpu_secs: Some(1),
+        };
+
+        assert_eq!(
+            limits.exceeded(&["memory allocation of 4096 bytes failed".into()]),
+            Some(LimitKind::Memory)
+        );
+        assert_eq!(
+            limits.exceeded(&["".into(), "Limit exceeded: CPU time".into()]),
+            Some(LimitKind::Cpu)
+        );
+        assert_eq!(limits.exceeded(&["thread 'main' panicked".into()]), None);
+        assert_eq!(
+            Limits::default().exceeded(&["Limit exceeded: CPU time".into()]),
+            None
+        );
+    }
+}
@@ -0,0 +1,207 @@
+/// Information about the machine that benchmarks were recorded on.
+/// Timings from different machines are not comparable, so stored timings are grouped by machine label.
+use std::{collections::HashMap, env, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+/// Overrides the host name as the label of the current machine, e.g. `ci`.
+const MACHINE_ENV: &str = "AOC_MACHINE";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Machine {
+    pub label: String,
+    pub cpu: Option<String>,
+    pub cores: Option<u64>,
+    pub rustc: Option<String>,
+    pub os: String,
+}
+
+impl Machine {
+    /// Collect information about the machine this process runs on.
+    pub fn detect() -> Self {
+        Self {
+            label: current_label(),
+            cpu: fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .and_then(|s| parse_cpu_model(&s)),
+            cores: thread::available_parallelism()
+                .ok()
+                .and_then(|n| u64::try_from(n.get()).ok()),
+            rustc: rustc_version(),
+            os: format!("{} {}", env::consts::OS, env::consts::ARCH),
+        }
+    }
+
+    /// A one-line summary, e.g. `laptop (AMD Ryzen 7 5800U, 16 cores, linux x86_64, rustc 1.83.0)`.
+    pub fn describe(&self) -> String {
+        let mut details: Vec<String> = vec![];
+
+        if let Some(cpu) = &self.cpu {
+            details.push(cpu.clone());
+        }
+        if let Some(cores) = self.cores {
+            details.push(format!(
+                "{cores} {}",
+                if cores == 1 { "core" } else { "cores" }
+            ));
+        }
+        details.push(self.os.clone());
+        if let Some(rustc) = &self.rustc {
+            details.push(rustc.clone());
+        }
+
+        format!("{} ({})", self.label, details.join(", "))
+    }
+}
+
+/// The label of the current machine: `AOC_MACHINE` if set, the host name otherwise.
+pub fn current_label() -> String {
+    env::var(MACHINE_ENV)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+        .or_else(host_name)
+        .unwrap_or_else(|| "unknown".into())
+}
+
+fn host_name() -> Option<String> {
+    let name = fs::read_to_string("/etc/hostname").ok().or_else(|| {
+        Command::new("hostname")
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+    })?;
+
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+fn rustc_version() -> Option<String> {
+    let output = Command::new("rustc")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+
+    // e.g. `rustc 1.83.0 (90b35a623 2024-11-26)`
+    let version = String::from_utf8_lossy(&output.stdout);
+    let version = version.split(" (").next()?.trim();
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .find(|line| line.starts_with("model name"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, model)| model.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|model| !model.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Machine> for JsonValue {
+    fn from(value: &Machine) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let optional_string = |x: &Option<String>| match x {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+
+        map.insert("label".into(), JsonValue::String(value.label.clone()));
+        map.insert("cpu".into(), optional_string(&value.cpu));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "cores".into(),
+            match value.cores {
+                Some(x) => JsonValue::Number(x as f64),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("rustc".into(), optional_string(&value.rustc));
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Machine {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected machine to be a JSON object.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let label = string("label").ok_or("Expected machine.label to be a string.")?;
+        let os = string("os").ok_or("Expected machine.os to be a string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64);
+
+        Ok(Machine {
+            label,
+            cpu: string("cpu"),
+            cores,
+            rustc: string("rustc"),
+            os,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{parse_cpu_model, Machine};
+
+    fn get_mock_machine() -> Machine {
+        Machine {
+            label: "ci".into(),
+            cpu: Some("AMD EPYC 7763 64-Core Processor".into()),
+            cores: Some(4),
+            rustc: Some("rustc 1.83.0".into()),
+            os: "linux x86_64".into(),
+        }
+    }
+
+    #[test]
+    fn parses_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD EPYC 7763   64-Core Processor\n\nprocessor\t: 1\nmodel name\t: AMD EPYC 7763 64-Core Processor\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo),
+            Some("AMD EPYC 7763 64-Core Processor".into())
+        );
+        assert_eq!(parse_cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn describes_machines() {
+        assert_eq!(
+            get_mock_machine().describe(),
+            "ci (AMD EPYC 7763 64-Core Processor, 4 cores, linux x86_64, rustc 1.83.0)"
+        );
+
+        let machine = Machine {
+            cpu: None,
+            cores: None,
+            rustc: None,
+            ..get_mock_machine()
+        };
+        assert_eq!(machine.describe(), "ci (linux x86_64)");
+    }
+
+    #[test]
+    fn roundtrips_machines() {
+        let machine = get_mock_machine();
+        let json = JsonValue::from(&machine);
+        assert_eq!(Machine::try_from(&json).unwrap(), machine);
+    }
+}
@@ -1,26 +1,49 @@
-use std::{env, fs};
+use std::{env, fs, path::PathBuf};
 
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod export;
+pub mod limits;
+pub mod profile;
+pub mod provider;
+pub mod report;
 pub mod runner;
 
 pub use day::*;
 
+mod answers;
+mod benchmark_chart;
+mod budget;
+mod cooldown;
 mod day;
+mod encryption;
+mod fingerprint;
+mod history;
+mod inputs;
+mod interrupt;
+mod machine;
+mod progress;
+mod puzzle_examples;
+mod puzzle_html;
+mod puzzle_render;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod submissions;
+mod table;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file to a string, decrypting it if it is encrypted.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = encryption::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
@@ -32,10 +55,53 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
         .join("data")
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = encryption::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Overrides the input file that a solution reads, used to bench a day against several inputs.
+pub const INPUT_FILE_ENV: &str = "AOC_INPUT_FILE";
+
+/// Helper function that reads the input of a day, or the file in `AOC_INPUT_FILE` if set.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    match env::var(INPUT_FILE_ENV) {
+        Ok(path) => encryption::read_to_string(path).expect("could not open input file"),
+        Err(_) => read_file("inputs", day),
+    }
+}
+
+/// Lists the input files of a day: `01.txt` first, followed by suffixed variants like `01-alice.txt`.
+#[must_use]
+pub fn input_variants(day: Day) -> Vec<PathBuf> {
+    let default = format!("{day}.txt");
+    let prefix = day.to_string();
+
+    let Ok(entries) = fs::read_dir(env::current_dir().unwrap().join("data").join("inputs")) else {
+        return vec![];
+    };
+
+    let mut variants: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+        })
+        .collect();
+
+    variants.sort_by_key(|path| {
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        (name != default, name)
+    });
+
+    variants
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
@@ -61,8 +127,10 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+            $crate::template::limits::apply_from_env();
+            let input = $crate::template::read_input(DAY);
             $( run_part($func, &input, DAY, $part); )*
+            print_peak_memory();
         }
     };
 }
@@ -0,0 +1,153 @@
+/// Cargo build profiles that solution binaries are compiled with.
+use std::{env, error::Error, fmt::Display, process::Command, str::FromStr};
+
+/// A named cargo profile, optionally compiled for the host CPU.
+///
+/// # Display
+/// This value displays as the profile name, suffixed with `+native` if applicable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub native: bool,
+}
+
+impl Profile {
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            native: false,
+        }
+    }
+
+    /// The default, unoptimized `dev` profile.
+    pub fn dev() -> Self {
+        Self::named("dev")
+    }
+
+    /// The optimized `release` profile.
+    pub fn release() -> Self {
+        Self::named("release")
+    }
+
+    /// The `bench` profile, which enables LTO and a single codegen unit. Used by `cargo time`.
+    pub fn bench() -> Self {
+        Self::named("bench")
+    }
+
+    /// Picks `release` or `dev` depending on the `--release` flag, unless a profile was passed explicitly.
+    pub fn resolve(profile: Option<Self>, release: bool) -> Self {
+        profile.unwrap_or_else(|| {
+            if release {
+                Self::release()
+            } else {
+                Self::dev()
+            }
+        })
+    }
+
+    /// Arguments that select this profile in a cargo invocation.
+    pub fn cargo_args(&self) -> Vec<String> {
+        match self.name.as_str() {
+            "dev" => vec![],
+            "release" => vec!["--release".into()],
+            name => vec!["--profile".into(), name.into()],
+        }
+    }
+
+    /// Configure a cargo command to build with this profile's compiler flags.
+    pub fn apply_env(&self, cmd: &mut Command) {
+        if self.native {
+            let rustflags = env::var("RUSTFLAGS").unwrap_or_default();
+            cmd.env(
+                "RUSTFLAGS",
+                format!("{rustflags} -C target-cpu=native").trim(),
+            );
+        }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.native {
+            write!(f, "{}+native", self.name)
+        } else {
+            f.write_str(&self.name)
+        }
+    }
+}
+
+impl FromStr for Profile {
+    type Err = ProfileFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, native) = match s.strip_suffix("+native") {
+            Some(name) => (name, true),
+            None => (s, false),
+        };
+
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(ProfileFromStrError);
+        }
+
+        Ok(Self {
+            name: name.into(),
+            native,
+        })
+    }
+}
+
+/// An error which can be returned when parsing a [`Profile`].
+#[derive(Debug)]
+pub struct ProfileFromStrError;
+
+impl Error for ProfileFromStrError {}
+
+impl Display for ProfileFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a cargo profile name, e.g. `release` or `bench`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::Profile;
+
+    #[test]
+    fn parses_profiles() {
+        assert_eq!(Profile::from_str("bench").unwrap(), Profile::bench());
+
+        let native = Profile::from_str("bench+native").unwrap();
+        assert_eq!(native.name, "bench");
+        assert!(native.native);
+        assert_eq!(native.to_string(), "bench+native");
+
+        assert!(Profile::from_str("").is_err());
+        assert!(Profile::from_str("+native").is_err());
+        assert!(Profile::from_str("my profile").is_err());
+    }
+
+    #[test]
+    fn builds_cargo_args() {
+        assert!(Profile::dev().cargo_args().is_empty());
+        assert_eq!(Profile::release().cargo_args(), ["--release"]);
+        assert_eq!(Profile::bench().cargo_args(), ["--profile", "bench"]);
+    }
+
+    #[test]
+    fn resolves_release_flag() {
+        assert_eq!(Profile::resolve(None, false), Profile::dev());
+        assert_eq!(Profile::resolve(None, true), Profile::release());
+        assert_eq!(
+            Profile::resolve(Some(Profile::bench()), false),
+            Profile::bench()
+        );
+    }
+}
@@ -0,0 +1,152 @@
+/// Local record of which puzzle parts were accepted by Advent of Code.
+/// Stored in `data/progress.json` as a map of days to accepted parts, e.g. `{ "01": [1, 2], "02": [1] }`.
+use std::{collections::BTreeMap, collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static PROGRESS_FILE_PATH: &str = "./data/progress.json";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// Whether part 1 and part 2 of a day were accepted.
+    pub stars: BTreeMap<Day, [bool; 2]>,
+}
+
+impl Progress {
+    /// Dehydrate progress to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(PROGRESS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate progress from a JSON file. If not present, returns empty progress.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(PROGRESS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Progress::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Record a part as accepted, or as not accepted if `accepted` is false.
+    pub fn set(&mut self, day: Day, part: u8, accepted: bool) {
+        let index = usize::from(part.clamp(1, 2) - 1);
+        let stars = self.stars.entry(day).or_default();
+        stars[index] = accepted;
+
+        if stars.iter().all(|s| !s) {
+            self.stars.remove(&day);
+        }
+    }
+
+    pub fn is_accepted(&self, day: Day, part: u8) -> bool {
+        self.stars
+            .get(&day)
+            .is_some_and(|stars| stars[usize::from(part.clamp(1, 2) - 1)])
+    }
+
+    /// Total number of accepted parts.
+    pub fn count_stars(&self) -> usize {
+        self.stars.values().flatten().filter(|s| **s).count()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Progress> for JsonValue {
+    fn from(value: &Progress) -> Self {
+        let map: HashMap<String, JsonValue> = value
+            .stars
+            .iter()
+            .map(|(day, stars)| {
+                let parts = (1..=2_u8)
+                    .zip(stars)
+                    .filter(|(_, accepted)| **accepted)
+                    .map(|(part, _)| JsonValue::Number(f64::from(part)))
+                    .collect();
+                (day.to_string(), JsonValue::Array(parts))
+            })
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Progress {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut progress = Progress::default();
+
+        for (day, parts) in json {
+            let day = Day::from_str(day).map_err(|_| format!("expected `{day}` to be a day."))?;
+
+            let parts = parts
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected accepted parts to be an array.")?;
+
+            for part in parts {
+                match part.get::<f64>() {
+                    Some(part) if *part == 1.0 || *part == 2.0 => {
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        progress.set(day, *part as u8, true);
+                    }
+                    _ => return Err("expected accepted parts to be 1 or 2.".into()),
+                }
+            }
+        }
+
+        Ok(progress)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Progress;
+    use crate::day;
+
+    #[test]
+    fn records_accepted_parts() {
+        let mut progress = Progress::default();
+        progress.set(day!(1), 1, true);
+        progress.set(day!(1), 2, true);
+        progress.set(day!(3), 1, true);
+
+        assert!(progress.is_accepted(day!(1), 2));
+        assert!(!progress.is_accepted(day!(3), 2));
+        assert!(!progress.is_accepted(day!(4), 1));
+        assert_eq!(progress.count_stars(), 3);
+
+        progress.set(day!(3), 1, false);
+        assert_eq!(progress.stars.len(), 1);
+    }
+
+    #[test]
+    fn roundtrips_progress() {
+        let mut progress = Progress::default();
+        progress.set(day!(1), 1, true);
+        progress.set(day!(1), 2, true);
+        progress.set(day!(2), 2, true);
+
+        let json = JsonValue::from(&progress).stringify().unwrap();
+        assert_eq!(Progress::try_from(json).unwrap(), progress);
+    }
+
+    #[test]
+    fn rejects_invalid_progress() {
+        assert!(Progress::try_from(r#"{ "01": [3] }"#.to_string()).is_err());
+        assert!(Progress::try_from(r#"{ "26": [1] }"#.to_string()).is_err());
+        assert!(Progress::try_from(r#"[]"#.to_string()).is_err());
+    }
+}
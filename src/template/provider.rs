@@ -0,0 +1,347 @@
+/// Where inputs and puzzles come from and where answers go to.
+/// The provider is selected with `AOC_PROVIDER`: `http` (default) talks to adventofcode.com directly,
+/// `aoc-cli` shells out to aoc-cli and `mirror` reads from a local copy in `AOC_MIRROR_DIR`.
+/// Providers are logged in to the default account, or to a named account to collect the inputs of teammates.
+use std::{
+    env,
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::template::{
+    aoc_cli::{self, AocCommandError},
+    aoc_client::{self, AocClient, AocClientError},
+    encryption,
+    inputs::{self, InvalidInput},
+    puzzle_html, Day,
+};
+
+#[derive(Debug)]
+pub enum ProviderError {
+    UnknownProvider(String),
+    MirrorNotSet,
+    /// The mirror has no copy of the requested file.
+    NotMirrored(PathBuf),
+    /// The provider can not submit answers.
+    ReadOnly,
+    /// The response is an error or login page rather than a puzzle input.
+    InvalidInput(InvalidInput),
+    /// Answers are blocked for a while, with the message of the response.
+    RateLimited(String),
+    Client(AocClientError),
+    Command(AocCommandError),
+    IO(io::Error),
+}
+
+impl Display for ProviderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProviderError::UnknownProvider(name) => write!(
+                f,
+                "unknown provider `{name}`, expected one of `http`, `aoc-cli` or `mirror`."
+            ),
+            ProviderError::MirrorNotSet => {
+                write!(
+                    f,
+                    "`AOC_MIRROR_DIR` must be set to use the mirror provider."
+                )
+            }
+            ProviderError::NotMirrored(path) => {
+                write!(f, "\"{}\" is not in the mirror.", path.display())
+            }
+            ProviderError::ReadOnly => write!(f, "the mirror provider can not submit answers."),
+            ProviderError::InvalidInput(e) => write!(f, "{e}"),
+            ProviderError::RateLimited(message) => write!(f, "rate limited: {message}"),
+            ProviderError::Client(e) => write!(f, "{e}"),
+            ProviderError::Command(e) => write!(f, "{e}"),
+            ProviderError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl ProviderError {
+    /// Whether retrying may succeed, e.g. right after a puzzle unlocked or when the connection dropped.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ProviderError::InvalidInput(InvalidInput::NotUnlocked | InvalidInput::NotFound)
+                | ProviderError::Client(
+                    AocClientError::HttpStatus(404 | 500..=599) | AocClientError::Transport(_)
+                )
+        )
+    }
+}
+
+impl From<AocClientError> for ProviderError {
+    fn from(e: AocClientError) -> Self {
+        match e {
+            AocClientError::RateLimited(Some(message)) => ProviderError::RateLimited(message),
+            AocClientError::InvalidInput(e) => ProviderError::InvalidInput(e),
+            e => ProviderError::Client(e),
+        }
+    }
+}
+
+impl From<AocCommandError> for ProviderError {
+    fn from(e: AocCommandError) -> Self {
+        ProviderError::Command(e)
+    }
+}
+
+impl From<io::Error> for ProviderError {
+    fn from(e: io::Error) -> Self {
+        ProviderError::IO(e)
+    }
+}
+
+pub trait PuzzleProvider {
+    /// The puzzle input of a day.
+    fn input(&self, day: Day) -> Result<String, ProviderError>;
+
+    /// The puzzle description of a day as markdown.
+    fn puzzle(&self, day: Day) -> Result<String, ProviderError>;
+
+    /// Submit an answer and return the message of the response.
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, ProviderError>;
+
+    /// Download the input and puzzle description of a day to `data/inputs` and `data/puzzles`, encrypted if a key is configured.
+    /// An existing input is kept unless `force` is set, the puzzle description is always refreshed.
+    /// For a named account, only its input is downloaded, e.g. to `data/inputs/01@alice.txt`.
+    fn download(&self, day: Day, account: Option<&str>, force: bool) -> Result<(), ProviderError> {
+        let input_path = inputs::get_input_path(day, account);
+        let puzzle_path = format!("data/puzzles/{day}.md");
+
+        let input = if !force && inputs::exists(day, account) {
+            None
+        } else {
+            let input = self.input(day)?;
+            inputs::validate(&input).map_err(ProviderError::InvalidInput)?;
+            Some(input)
+        };
+        let puzzle = match account {
+            Some(_) => None,
+            None => Some(self.puzzle(day)?),
+        };
+
+        if let Some(input) = input {
+            inputs::save(day, account, &input)?;
+            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        } else {
+            println!(
+                "🎄 Kept existing input \"{input_path}\", pass `--force` to download it again."
+            );
+            if inputs::is_modified(day, account) {
+                eprintln!("Warning: \"{input_path}\" was changed since it was downloaded.");
+            }
+        }
+
+        if let Some(puzzle) = puzzle {
+            encryption::write(&puzzle_path, &puzzle)?;
+            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        }
+        Ok(())
+    }
+
+    /// Fetch the puzzle description of a day and save it to `data/puzzles`.
+    fn save_puzzle(&self, day: Day) -> Result<String, ProviderError> {
+        let puzzle = self.puzzle(day)?;
+        encryption::write(format!("data/puzzles/{day}.md"), &puzzle)?;
+        Ok(puzzle)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl PuzzleProvider for AocClient {
+    fn input(&self, day: Day) -> Result<String, ProviderError> {
+        Ok(AocClient::input(self, day)?)
+    }
+
+    fn puzzle(&self, day: Day) -> Result<String, ProviderError> {
+        Ok(AocClient::puzzle(self, day)?)
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, ProviderError> {
+        Ok(AocClient::submit(self, day, part, answer)?)
+    }
+}
+
+/// Shells out to aoc-cli, see: https://github.com/scarvalhojr/aoc-cli
+/// Uses the session file of aoc-cli, or the given one for a named account.
+pub struct AocCli {
+    session_file: Option<PathBuf>,
+}
+
+impl PuzzleProvider for AocCli {
+    fn input(&self, day: Day) -> Result<String, ProviderError> {
+        Ok(aoc_cli::input(day, self.session_file.as_deref())?)
+    }
+
+    fn puzzle(&self, day: Day) -> Result<String, ProviderError> {
+        Ok(aoc_cli::puzzle(day)?)
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, ProviderError> {
+        let message = aoc_cli::submit(day, part, answer)?;
+
+        if message.contains("You gave an answer too recently") {
+            return Err(ProviderError::RateLimited(message));
+        }
+
+        Ok(message)
+    }
+}
+
+/// A read-only local copy of inputs and puzzles, e.g. to work offline or to run commands in tests.
+/// Expects `inputs/01.txt` and either `puzzles/01.md` or a saved puzzle page `puzzles/01.html`.
+/// Inputs of named accounts are mirrored as `inputs/01@alice.txt`. Encrypted copies are decrypted.
+pub struct Mirror {
+    dir: PathBuf,
+    account: Option<String>,
+}
+
+impl Mirror {
+    pub fn new(dir: &Path, account: Option<&str>) -> Self {
+        Mirror {
+            dir: dir.to_path_buf(),
+            account: account.map(String::from),
+        }
+    }
+
+    fn read(&self, path: PathBuf) -> Result<String, ProviderError> {
+        encryption::read_to_string(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => ProviderError::NotMirrored(path),
+            _ => ProviderError::IO(e),
+        })
+    }
+}
+
+impl PuzzleProvider for Mirror {
+    fn input(&self, day: Day) -> Result<String, ProviderError> {
+        let name = inputs::input_name(day, self.account.as_deref());
+        self.read(self.dir.join("inputs").join(format!("{name}.txt")))
+    }
+
+    fn puzzle(&self, day: Day) -> Result<String, ProviderError> {
+        let markdown = self.dir.join("puzzles").join(format!("{day}.md"));
+        let html = self.dir.join("puzzles").join(format!("{day}.html"));
+
+        if !markdown.exists() && html.exists() {
+            return self.read(html).map(|html| puzzle_html::to_markdown(&html));
+        }
+
+        self.read(markdown)
+    }
+
+    fn submit(&self, _day: Day, _part: u8, _answer: &str) -> Result<String, ProviderError> {
+        Err(ProviderError::ReadOnly)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProviderKind {
+    #[default]
+    Http,
+    AocCli,
+    Mirror,
+}
+
+impl FromStr for ProviderKind {
+    type Err = ProviderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" | "http" => Ok(ProviderKind::Http),
+            "aoc-cli" => Ok(ProviderKind::AocCli),
+            "mirror" => Ok(ProviderKind::Mirror),
+            _ => Err(ProviderError::UnknownProvider(s.to_string())),
+        }
+    }
+}
+
+/// Create the provider selected by `AOC_PROVIDER`, logged in to the default or a named account.
+pub fn from_env(account: Option<&str>) -> Result<Box<dyn PuzzleProvider>, ProviderError> {
+    let kind = match env::var("AOC_PROVIDER") {
+        Ok(name) => name.parse()?,
+        Err(_) => ProviderKind::default(),
+    };
+
+    Ok(match kind {
+        ProviderKind::Http => Box::new(AocClient::from_env(account)?),
+        ProviderKind::AocCli => {
+            aoc_cli::check()?;
+            Box::new(AocCli {
+                session_file: account.and_then(|account| aoc_client::session_file(Some(account))),
+            })
+        }
+        ProviderKind::Mirror => {
+            let dir = env::var_os("AOC_MIRROR_DIR").ok_or(ProviderError::MirrorNotSet)?;
+            Box::new(Mirror::new(Path::new(&dir), account))
+        }
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{Mirror, ProviderError, ProviderKind, PuzzleProvider};
+    use crate::day;
+
+    #[test]
+    fn parses_provider_kinds() {
+        assert_eq!("http".parse::<ProviderKind>().unwrap(), ProviderKind::Http);
+        assert_eq!(
+            "aoc-cli".parse::<ProviderKind>().unwrap(),
+            ProviderKind::AocCli
+        );
+        assert_eq!(
+            "mirror".parse::<ProviderKind>().unwrap(),
+            ProviderKind::Mirror
+        );
+        assert!(matches!(
+            "ftp".parse::<ProviderKind>(),
+            Err(ProviderError::UnknownProvider(_))
+        ));
+    }
+
+    #[test]
+    fn reads_from_mirror() {
+        let dir = env::temp_dir().join(format!("aoc-mirror-{}", process::id()));
+        fs::create_dir_all(dir.join("inputs")).unwrap();
+        fs::create_dir_all(dir.join("puzzles")).unwrap();
+        fs::write(dir.join("inputs").join("01.txt"), "3   4\n").unwrap();
+        fs::write(dir.join("inputs").join("01@alice.txt"), "5   6\n").unwrap();
+        fs::write(dir.join("puzzles").join("01.md"), "## --- Day 1 ---").unwrap();
+        fs::write(
+            dir.join("puzzles").join("02.html"),
+            "<main><article><h2>--- Day 2 ---</h2></article></main>",
+        )
+        .unwrap();
+
+        let mirror = Mirror::new(&dir, None);
+        assert_eq!(mirror.input(day!(1)).unwrap(), "3   4\n");
+        assert_eq!(
+            Mirror::new(&dir, Some("alice")).input(day!(1)).unwrap(),
+            "5   6\n"
+        );
+        assert_eq!(mirror.puzzle(day!(1)).unwrap(), "## --- Day 1 ---");
+        assert_eq!(mirror.puzzle(day!(2)).unwrap(), "## --- Day 2 ---");
+        assert!(matches!(
+            mirror.input(day!(2)),
+            Err(ProviderError::NotMirrored(_))
+        ));
+        assert!(matches!(
+            mirror.submit(day!(1), 1, "11"),
+            Err(ProviderError::ReadOnly)
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,225 @@
+/// Extracts example inputs and their expected answers from a downloaded puzzle description.
+/// Examples are written to `data/examples`, answers are filled into the tests of a scaffolded solution.
+use std::fs;
+
+use crate::template::{encryption, Day};
+
+pub const PART_TWO_HEADING: &str = "--- Part Two ---";
+const TEST_PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+/// The code blocks of a puzzle that follow a mention of an example, without duplicates.
+/// Falls back to the first code block if no block is introduced as an example.
+pub fn extract_examples(markdown: &str) -> Vec<String> {
+    let mut blocks: Vec<(bool, String)> = vec![];
+    let mut paragraph = String::new();
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        match (&mut block, line.trim_end() == "```") {
+            (None, true) => block = Some(String::new()),
+            (Some(contents), true) => {
+                let is_example = paragraph.to_lowercase().contains("example");
+                blocks.push((is_example, std::mem::take(contents)));
+                block = None;
+            }
+            (Some(contents), false) => {
+                contents.push_str(line);
+                contents.push('\n');
+            }
+            (None, false) if !line.trim().is_empty() => paragraph = line.to_string(),
+            (None, false) => {}
+        }
+    }
+
+    let mut examples: Vec<String> = vec![];
+    let candidates = if blocks.iter().any(|(is_example, _)| *is_example) {
+        blocks
+            .into_iter()
+            .filter(|(is_example, _)| *is_example)
+            .collect()
+    } else {
+        blocks.into_iter().take(1).collect::<Vec<_>>()
+    };
+
+    for (_, contents) in candidates {
+        if !contents.trim().is_empty() && !examples.contains(&contents) {
+            examples.push(contents);
+        }
+    }
+
+    examples
+}
+
+/// The expected example answer of each part: the last emphasized number in the part's description
+/// after it mentions an example. Understands both *`42`* and `*42*`.
+pub fn extract_answers(markdown: &str) -> [Option<String>; 2] {
+    let (part_one, part_two) = match markdown.find(PART_TWO_HEADING) {
+        Some(i) => (&markdown[..i], Some(&markdown[i..])),
+        None => (markdown, None),
+    };
+
+    [Some(part_one), part_two].map(|part| part.and_then(last_emphasized_number))
+}
+
+fn last_emphasized_number(section: &str) -> Option<String> {
+    let start = section.to_lowercase().find("example")?;
+    let section = &section[start..];
+
+    let mut answer = None;
+
+    for pattern in [("*`", "`*"), ("`*", "*`")] {
+        let mut rest = section;
+        while let Some(i) = rest.find(pattern.0) {
+            let after = &rest[i + pattern.0.len()..];
+            let Some(end) = after.find(pattern.1) else {
+                break;
+            };
+            let candidate = &after[..end];
+            if !candidate.is_empty() && candidate.chars().all(|c| c.is_ascii_digit()) {
+                let position = section.len() - rest.len() + i;
+                if answer.as_ref().is_none_or(|(p, _)| position > *p) {
+                    answer = Some((position, candidate.to_string()));
+                }
+            }
+            rest = &after[end + pattern.1.len()..];
+        }
+    }
+
+    answer.map(|(_, answer)| answer)
+}
+
+/// Replace the `None` placeholder in the test of a part with the expected answer.
+/// Returns `None` if the test was already filled in.
+fn fill_test(module: &str, part: u8, answer: &str) -> Option<String> {
+    let name = if part == 1 {
+        "fn test_part_one"
+    } else {
+        "fn test_part_two"
+    };
+    let start = module.find(name)?;
+    let end = module[start + name.len()..]
+        .find("fn ")
+        .map_or(module.len(), |i| start + name.len() + i);
+
+    let offset = module[start..end].find(TEST_PLACEHOLDER)? + start;
+
+    Some(format!(
+        "{}assert_eq!(result, Some({answer}));{}",
+        &module[..offset],
+        &module[offset + TEST_PLACEHOLDER.len()..]
+    ))
+}
+
+/// Write the examples of a downloaded puzzle to `data/examples` and fill the expected answers into its tests.
+/// Example files and tests that were already filled in are left alone.
+pub fn update(day: Day) {
+    let Ok(markdown) = encryption::read_to_string(format!("data/puzzles/{day}.md")) else {
+        return;
+    };
+
+    for (i, example) in extract_examples(&markdown).iter().enumerate() {
+        let path = if i == 0 {
+            format!("data/examples/{day}.txt")
+        } else {
+            format!("data/examples/{day}-{}.txt", i + 1)
+        };
+
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+        if is_empty && fs::write(&path, example).is_ok() {
+            println!("🎄 Extracted example to \"{path}\".");
+        }
+    }
+
+    let module_path = format!("src/bin/{day}.rs");
+    let Ok(mut module) = fs::read_to_string(&module_path) else {
+        return;
+    };
+
+    let mut filled = vec![];
+    for (part, answer) in (1..=2).zip(extract_answers(&markdown)) {
+        if let Some(updated) = answer.as_ref().and_then(|a| fill_test(&module, part, a)) {
+            module = updated;
+            filled.push(format!("part {part}: {}", answer.unwrap()));
+        }
+    }
+
+    if !filled.is_empty() && fs::write(&module_path, module).is_ok() {
+        println!(
+            "🎄 Filled expected example answers into \"{module_path}\" ({}).",
+            filled.join(", ")
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_answers, extract_examples, fill_test};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+The lists are not very similar.
+
+```
+ignored
+```
+
+For example:
+
+```
+3   4
+4   3
+```
+
+In the example above, the pairs are `3` and `3`, a distance of *`0`*. The total distance is *`11`*.
+
+## --- Part Two ---
+
+Consider the example again:
+
+```
+3   4
+4   3
+```
+
+So, for this example, the similarity score is `*31*`.";
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(extract_examples(PUZZLE), vec!["3   4\n4   3\n".to_string()]);
+
+        let markdown = "Two examples:\n\n```\na\n```\n\nAnother example:\n\n```\nb\n```";
+        assert_eq!(extract_examples(markdown), vec!["a\n", "b\n"]);
+
+        assert_eq!(
+            extract_examples("Text\n\n```\na\n```\n\n```\nb\n```"),
+            vec!["a\n"]
+        );
+    }
+
+    #[test]
+    fn extracts_answers() {
+        assert_eq!(
+            extract_answers(PUZZLE),
+            [Some("11".into()), Some("31".into())]
+        );
+
+        let part_one = PUZZLE.split("## --- Part Two").next().unwrap();
+        assert_eq!(extract_answers(part_one), [Some("11".into()), None]);
+        assert_eq!(extract_answers("*`11`* without examples"), [None, None]);
+    }
+
+    #[test]
+    fn fills_tests() {
+        let module = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+        let module = fill_test(module, 2, "31").unwrap();
+        assert!(module.contains("fn test_part_one() {\n        let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));\n        assert_eq!(result, None);"));
+        assert!(module.contains("assert_eq!(result, Some(31));"));
+
+        let module = fill_test(&module, 1, "11").unwrap();
+        assert!(module.contains("assert_eq!(result, Some(11));"));
+        assert_eq!(fill_test(&module, 1, "11"), None);
+    }
+}
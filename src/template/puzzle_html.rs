@@ -0,0 +1,242 @@
+//! Converts the HTML of a puzzle page on adventofcode.com to markdown.
+//! Only the puzzle descriptions (`<article>` elements) are kept, the page chrome is dropped.
+
+/// Convert the descriptions on a puzzle page to markdown.
+pub fn to_markdown(html: &str) -> String {
+    articles(html)
+        .iter()
+        .map(|article| convert(article))
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Convert the first `<article>` of a page to plain text, e.g. the message of an answer submission.
+pub fn article_text(html: &str) -> Option<String> {
+    articles(html).first().map(|article| convert(article))
+}
+
+/// The inner HTML of every `<article>` element.
+fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(end) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        articles.push(&rest[content_start..content_start + end]);
+        rest = &rest[content_start + end + "</article>".len()..];
+    }
+
+    articles
+}
+
+fn convert(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    // start of the current inline code span in `out` and whether it contains emphasis.
+    let mut code: Option<(usize, bool)> = None;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_end) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_end];
+        rest = &rest[tag_start + tag_end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", false) if !in_pre => {
+                code = Some((out.len(), false));
+                out.push('`');
+            }
+            ("code", true) if !in_pre => {
+                out.push('`');
+                // emphasized code, which usually marks an answer, becomes *`code`*.
+                if let Some((start, true)) = code {
+                    out.insert(start, '*');
+                    out.push('*');
+                }
+                code = None;
+            }
+            ("em", false) if !in_pre => match &mut code {
+                Some((_, emphasized)) => *emphasized = true,
+                None => out.push('*'),
+            },
+            ("em", true) if !in_pre && code.is_none() => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li" | "br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    tidy(&out)
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+
+    // outside of code blocks, whitespace is collapsed like a browser would.
+    if text.starts_with(char::is_whitespace) && !out.is_empty() && !out.ends_with([' ', '\n']) {
+        out.push(' ');
+    }
+
+    out.push_str(&text.split_whitespace().collect::<Vec<_>>().join(" "));
+
+    if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+        out.push(' ');
+    }
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Trim trailing whitespace of every line and collapse runs of blank lines.
+fn tidy(s: &str) -> String {
+    let mut lines: Vec<&str> = vec![];
+
+    for line in s.lines().map(str::trim_end) {
+        if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+
+    lines.join("\n").trim_end().to_string()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{article_text, to_markdown};
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = r#"<html><body><header>Advent of Code</header><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is
+always present.</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>The answer is <code>11</code>, or <code><em>31</em></code> &amp; more.</p>
+<ul>
+<li>The first item.</li>
+<li>The <a href="/2024/day/1/input">second</a> item.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>42</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Part &lt;two&gt;.</p></article>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "The *Chief Historian* is always present.",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "The answer is `11`, or *`31`* & more.",
+            "",
+            "- The first item.",
+            "- The second item.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Part <two>.",
+        ]
+        .join("\n");
+
+        assert_eq!(to_markdown(html), expected);
+    }
+
+    #[test]
+    fn extracts_submission_messages() {
+        let html = "<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer. <a href=\"/2024\">[Return]</a></p></article></main>";
+        assert_eq!(
+            article_text(html).unwrap(),
+            "That's the right answer! You are one gold star closer. [Return]"
+        );
+        assert_eq!(article_text("<main></main>"), None);
+    }
+}
@@ -0,0 +1,230 @@
+//! Renders the markdown of a downloaded puzzle in the terminal.
+//! Headings and emphasis are printed in bold, code blocks are indented in italics and paragraphs are wrapped to the terminal width.
+
+use std::env;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const DEFAULT_WIDTH: usize = 80;
+const MAX_WIDTH: usize = 100;
+const CODE_INDENT: &str = "    ";
+
+/// The width to wrap at, taken from the terminal attached to stdout or from `COLUMNS` if the shell exports it.
+pub fn terminal_width() -> usize {
+    tty_columns()
+        .or_else(|| {
+            env::var("COLUMNS")
+                .ok()
+                .and_then(|columns| columns.trim().parse().ok())
+        })
+        .filter(|columns| *columns > 0)
+        .map_or(DEFAULT_WIDTH, |columns: usize| columns.min(MAX_WIDTH))
+}
+
+#[cfg(unix)]
+fn tty_columns() -> Option<usize> {
+    let mut size = std::mem::MaybeUninit::<libc::winsize>::zeroed();
+    // SAFETY: `size` is a valid pointer to a `winsize` struct that `ioctl` fills.
+    if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, size.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    // SAFETY: `ioctl` succeeded, so the struct is initialized.
+    let columns = unsafe { size.assume_init() }.ws_col;
+    (columns > 0).then_some(usize::from(columns))
+}
+
+#[cfg(not(unix))]
+fn tty_columns() -> Option<usize> {
+    None
+}
+
+/// Render puzzle markdown as produced by `puzzle_html::to_markdown` or aoc-cli.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut out: Vec<String> = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            flush(&mut out, &mut paragraph, width);
+            in_code = !in_code;
+            continue;
+        }
+
+        if in_code {
+            out.push(format!("{CODE_INDENT}{ANSI_ITALIC}{line}{ANSI_RESET}"));
+        } else if let Some(heading) = line.strip_prefix('#') {
+            flush(&mut out, &mut paragraph, width);
+            let heading = heading.trim_start_matches('#').trim();
+            out.push(format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+        } else if line.trim().is_empty() {
+            flush(&mut out, &mut paragraph, width);
+            if out.last().is_some_and(|l| !l.is_empty()) {
+                out.push(String::new());
+            }
+        } else if is_list_item(line) {
+            flush(&mut out, &mut paragraph, width);
+            paragraph.push(line);
+        } else {
+            paragraph.push(line);
+        }
+    }
+
+    flush(&mut out, &mut paragraph, width);
+    out.join("\n").trim_end().to_string()
+}
+
+fn is_list_item(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("- ") || line.starts_with("* ")
+}
+
+/// Wrap the pending lines of a paragraph or list item, with a hanging indent for list items.
+fn flush(out: &mut Vec<String>, paragraph: &mut Vec<&str>, width: usize) {
+    if paragraph.is_empty() {
+        return;
+    }
+
+    let text = paragraph.join(" ");
+    paragraph.clear();
+
+    let (prefix, text) = match text.trim_start() {
+        t if is_list_item(t) => ("  - ", &t[2..]),
+        t => ("", t),
+    };
+    let indent = " ".repeat(prefix.len());
+
+    for (i, line) in wrap(&styled_words(text), width.saturating_sub(prefix.len()))
+        .into_iter()
+        .enumerate()
+    {
+        out.push(format!("{}{line}", if i == 0 { prefix } else { &indent }));
+    }
+}
+
+/// Split text into words of characters that know whether they are emphasized.
+/// Code spans are kept verbatim, `*` and `**` toggle emphasis and `\` escapes the next character.
+fn styled_words(text: &str) -> Vec<Vec<(char, bool)>> {
+    let mut words = vec![];
+    let mut word = vec![];
+    let mut bold = false;
+    let mut in_code = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '`' => in_code = !in_code,
+            '\\' if !in_code => {
+                if let Some(next) = chars.next() {
+                    word.push((next, bold));
+                }
+            }
+            '*' if !in_code => {
+                chars.next_if_eq(&'*');
+                bold = !bold;
+            }
+            c if c.is_whitespace() && !in_code => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push((c, bold)),
+        }
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Greedily wrap words to `width` visible characters, opening and closing bold runs on every line.
+fn wrap(words: &[Vec<(char, bool)>], width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line: Vec<(char, bool)> = vec![];
+
+    for word in words {
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(style(&line));
+            line.clear();
+        }
+        if !line.is_empty() {
+            // spaces between two emphasized words stay emphasized.
+            let bold = line.last().is_some_and(|c| c.1) && word.first().is_some_and(|c| c.1);
+            line.push((' ', bold));
+        }
+        line.extend(word);
+    }
+
+    if !line.is_empty() {
+        lines.push(style(&line));
+    }
+
+    lines
+}
+
+fn style(chars: &[(char, bool)]) -> String {
+    let mut out = String::new();
+    let mut bold = false;
+
+    for (c, is_bold) in chars {
+        if *is_bold != bold {
+            out.push_str(if *is_bold { ANSI_BOLD } else { ANSI_RESET });
+            bold = *is_bold;
+        }
+        out.push(*c);
+    }
+
+    if bold {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn renders_headings_and_code() {
+        let markdown =
+            "## --- Day 1: Historian Hysteria ---\n\nFor example:\n\n```\n3   4\n4   3\n```";
+        let expected = format!(
+            "{ANSI_BOLD}--- Day 1: Historian Hysteria ---{ANSI_RESET}\n\nFor example:\n\n    {ANSI_ITALIC}3   4{ANSI_RESET}\n    {ANSI_ITALIC}4   3{ANSI_RESET}"
+        );
+        assert_eq!(render(markdown, 80), expected);
+    }
+
+    #[test]
+    fn renders_emphasis_and_inline_code() {
+        assert_eq!(
+            render("The total is *`11`*, not `12`.", 80),
+            format!("The total is {ANSI_BOLD}11{ANSI_RESET}, not 12.")
+        );
+        assert_eq!(
+            render("The **Chief Historian** is \\*here\\*.", 80),
+            format!("The {ANSI_BOLD}Chief Historian{ANSI_RESET} is *here*.")
+        );
+    }
+
+    #[test]
+    fn wraps_paragraphs_and_lists() {
+        assert_eq!(
+            render("one two three four\nfive six", 10),
+            "one two\nthree four\nfive six"
+        );
+        assert_eq!(
+            render("- one two three four\n- five", 12),
+            "  - one two\n    three\n    four\n  - five"
+        );
+        assert_eq!(
+            render("a *b c d* e", 4),
+            format!("a {ANSI_BOLD}b{ANSI_RESET}\n{ANSI_BOLD}c d{ANSI_RESET}\ne")
+        );
+    }
+}
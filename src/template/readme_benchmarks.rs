@@ -1,12 +1,158 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashSet, env, fmt::Display, fs, io, path::Path, str::FromStr};
 
-use crate::template::timings::Timings;
+use crate::template::benchmark_chart::{self, Scale, CHART_PATH};
+use crate::template::timings::{Timing, Timings};
 use crate::template::Day;
+use crate::template::{fingerprint, machine};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Selects the machine whose timings are shown in the readme, defaults to the current machine.
+const README_MACHINE_ENV: &str = "AOC_README_MACHINE";
+/// Comma-separated list of table columns, e.g. `part_1,part_2,samples,memory`.
+const README_COLUMNS_ENV: &str = "AOC_README_COLUMNS";
+
+/// An optional column of the benchmark table. The `Day` column is always shown first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Part1,
+    Part2,
+    Samples,
+    Memory,
+    Verified,
+    Lines,
+    Share,
+}
+
+impl Column {
+    pub const DEFAULT: [Self; 2] = [Column::Part1, Column::Part2];
+
+    /// Read the columns from the `AOC_README_COLUMNS` environment variable, defaults to both parts.
+    pub fn from_env() -> Vec<Self> {
+        let Ok(value) = env::var(README_COLUMNS_ENV) else {
+            return Self::DEFAULT.to_vec();
+        };
+
+        let columns: Vec<Self> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| {
+                Self::from_str(s)
+                    .map_err(|e| eprintln!("Ignoring readme column `{s}`: {e}"))
+                    .ok()
+            })
+            .collect();
+
+        if columns.is_empty() {
+            Self::DEFAULT.to_vec()
+        } else {
+            columns
+        }
+    }
+
+    fn header(self) -> &'static str {
+        match self {
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Samples => "Samples",
+            Column::Memory => "Peak memory",
+            Column::Verified => "Verified",
+            Column::Lines => "Lines",
+            Column::Share => "Share",
+        }
+    }
+
+    fn cell(self, timing: &Timing, total_nanos: f64) -> String {
+        let code = |s: Option<String>| format!("`{}`", s.unwrap_or_else(|| "-".into()));
+        let count = |n: Option<u64>| n.map_or_else(|| "-".into(), |n| n.to_string());
+
+        match self {
+            Column::Part1 => code(timing.part_1.clone()),
+            Column::Part2 => code(timing.part_2.clone()),
+            Column::Samples => format!(
+                "{} / {}",
+                count(timing.part_1_samples),
+                count(timing.part_2_samples)
+            ),
+            Column::Memory => code(timing.peak_memory_kb.map(format_memory)),
+            Column::Verified => match timing.verified {
+                Some(true) => "✅".into(),
+                Some(false) => "❌".into(),
+                None => "-".into(),
+            },
+            Column::Lines => count(
+                fs::read_to_string(get_path_for_bin(timing.day))
+                    .ok()
+                    .map(|s| count_code_lines(&s)),
+            ),
+            Column::Share => {
+                if total_nanos > 0.0 {
+                    format!("{:.1}%", timing.total_nanos / total_nanos * 100.0)
+                } else {
+                    "-".into()
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = ColumnFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "samples" => Ok(Column::Samples),
+            "memory" => Ok(Column::Memory),
+            "verified" => Ok(Column::Verified),
+            "lines" => Ok(Column::Lines),
+            "share" => Ok(Column::Share),
+            _ => Err(ColumnFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Column`].
+#[derive(Debug)]
+pub struct ColumnFromStrError;
+
+impl std::error::Error for ColumnFromStrError {}
+
+impl Display for ColumnFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting one of `part_1`, `part_2`, `samples`, `memory`, `verified`, `lines` or `share`",
+        )
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_memory(kb: u64) -> String {
+    if kb < 1024 {
+        format!("{kb} KiB")
+    } else {
+        format!("{:.1} MiB", kb as f64 / 1024.0)
+    }
+}
+
+/// Count the lines of solution code, ignoring blank lines, comments and the test module.
+fn count_code_lines(source: &str) -> u64 {
+    let mut count = 0;
+    for line in source.lines().map(str::trim) {
+        if line.starts_with("#[cfg(test)]") {
+            break;
+        }
+        if !line.is_empty() && !line.starts_with("//") {
+            count += 1;
+        }
+    }
+    count
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -52,78 +198,181 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// Optional parts of the benchmark table.
+struct TableOptions<'a> {
+    /// Path of the benchmark chart to embed below the table.
+    chart_path: Option<&'a str>,
+    columns: Vec<Column>,
+    /// Days whose code or input changed since they were benched.
+    stale_days: HashSet<Day>,
+}
+
+impl Default for TableOptions<'_> {
+    fn default() -> Self {
+        Self {
+            chart_path: None,
+            columns: Column::DEFAULT.to_vec(),
+            stale_days: HashSet::new(),
+        }
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> String {
+    let TableOptions {
+        chart_path,
+        columns,
+        stale_days,
+    } = options;
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if let Some(machine) = timings.machines.first() {
+        lines.push(format!("_Measured on {}._", machine.describe()));
+        lines.push(String::new());
+    }
+
+    let headers: Vec<&str> = columns.iter().map(|c| c.header()).collect();
+    let alignments = vec![":---:"; columns.len()];
 
-    for timing in timings.data {
+    lines.push(format!("| Day | {} |", headers.join(" | ")));
+    lines.push(format!("| :---: | {}  |", alignments.join(" | ")));
+
+    let total_nanos = timings
+        .data
+        .iter()
+        .fold(0_f64, |acc, t| acc + t.total_nanos);
+
+    for timing in &timings.data {
         let path = get_path_for_bin(timing.day);
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|c| c.cell(timing, total_nanos))
+            .collect();
+        let stale = if stale_days.contains(&timing.day) {
+            " ⚠️"
+        } else {
+            ""
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}){stale} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cells.join(" | ")
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if !stale_days.is_empty() {
+        lines.push(String::new());
+        lines.push(
+            "⚠️ _Code or input changed since these timings were recorded. Run `cargo time --store` to update them._"
+                .into(),
+        );
+    }
+
+    if let Some(chart_path) = chart_path {
+        lines.push(String::new());
+        lines.push(format!("![Benchmark chart]({chart_path})"));
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+pub fn update(timings: &Timings) -> Result<(), Error> {
+    update_in(Path::new("."), timings)
+}
+
+/// Update the readme and benchmark chart of the project in `dir`.
+fn update_in(dir: &Path, timings: &Timings) -> Result<(), Error> {
+    let path = dir.join("README.md");
+    let mut readme = String::from_utf8_lossy(&fs::read(&path)?).to_string();
+
+    let label = env::var(README_MACHINE_ENV)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(machine::current_label);
+    let timings = timings.for_machine(&label);
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+
+    let stale_days: HashSet<Day> = timings
+        .data
+        .iter()
+        .map(|t| t.day)
+        .filter(|day| timings.is_day_stale(*day, &fingerprint::day_hash(*day)))
+        .collect();
+
+    let chart = benchmark_chart::render(&timings, Scale::from_env());
+    let chart_path = dir.join(CHART_PATH);
+    if let Some(dir) = chart_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(chart_path, chart)?;
+
+    update_content(
+        &mut readme,
+        timings,
+        total_millis,
+        &TableOptions {
+            chart_path: Some(CHART_PATH),
+            columns: Column::from_env(),
+            stale_days,
+        },
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::{collections::HashSet, env, fs, process};
+
+    use super::{count_code_lines, update_content, update_in, Column, TableOptions, MARKER};
+    use crate::{
+        day,
+        template::benchmark_chart::CHART_PATH,
+        template::machine::Machine,
+        template::timings::{mock_timing, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..mock_timing(day!(1), Some("10ms"), Some("20ms"))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..mock_timing(day!(2), Some("30ms"), Some("40ms"))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..mock_timing(day!(4), Some("40ms"), Some("50ms"))
                 },
             ],
+            machines: vec![],
         }
     }
 
@@ -131,28 +380,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +409,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +429,135 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn names_benchmark_machine() {
+        let machine = Machine {
+            label: "ci".into(),
+            cpu: None,
+            cores: Some(4),
+            rustc: None,
+            os: "linux x86_64".into(),
+        };
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            get_mock_timings().with_machine(&machine),
+            190.0,
+            &TableOptions::default(),
+        )
+        .unwrap();
+        assert!(s.contains(
+            "## Benchmarks\n\n_Measured on ci (4 cores, linux x86_64)._\n\n| Day | Part 1 | Part 2 |"
+        ));
+    }
+
+    #[test]
+    fn embeds_benchmark_chart() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableOptions {
+                chart_path: Some("./.assets/benchmarks.svg"),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(s.contains(
+            "**Total: 190.00ms**\n\n![Benchmark chart](./.assets/benchmarks.svg)\n<!--- benchmarking table --->"
+        ));
+    }
+
+    #[test]
+    fn formats_extra_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_samples = Some(1000);
+        timings.data[0].part_2_samples = Some(10);
+        timings.data[0].peak_memory_kb = Some(2560);
+        timings.data[0].verified = Some(true);
+        timings.data[1].peak_memory_kb = Some(512);
+        timings.data[1].verified = Some(false);
+
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            190.0,
+            &TableOptions {
+                columns: vec![
+                    Column::Part1,
+                    Column::Samples,
+                    Column::Memory,
+                    Column::Verified,
+                    Column::Share,
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(s.contains(
+            "| Day | Part 1 | Samples | Peak memory | Verified | Share |\n| :---: | :---: | :---: | :---: | :---: | :---:  |"
+        ));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | 1000 / 10 | `2.5 MiB` | ✅ | 15.8% |"
+        ));
+        assert!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | - / - | `512 KiB` | ❌ | 36.8% |")
+        );
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `40ms` | - / - | `-` | - | 47.4% |"));
+    }
+
+    #[test]
+    fn shows_timings_stored_before_machines_were_recorded() {
+        let dir = env::temp_dir().join(format!("aoc-readme-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("README.md"),
+            format!("# readme\n{MARKER}{MARKER}\n"),
+        )
+        .unwrap();
+
+        let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "part_1_nanos": 1000000, "part_2_nanos": 2000000, "total_nanos": 3000000 }] }"#.to_string();
+        update_in(&dir, &Timings::try_from(json).unwrap()).unwrap();
+
+        let readme = fs::read_to_string(dir.join("README.md")).unwrap();
+        assert!(readme.contains("| [Day 1](./src/bin/01.rs) | `1ms` | `2ms` |"));
+        assert!(readme.contains("**Total: 3.00ms**"));
+        assert!(dir.join(CHART_PATH).exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn parses_columns() {
+        assert_eq!("samples".parse::<Column>().unwrap(), Column::Samples);
+        assert!("foo".parse::<Column>().is_err());
+    }
+
+    #[test]
+    fn counts_code_lines() {
+        let source = "use std::fs;\n\n// a comment\npub fn part_one() {\n    1\n}\n\n#[cfg(test)]\nmod tests {\n}\n";
+        assert_eq!(count_code_lines(source), 4);
+    }
+
+    #[test]
+    fn marks_stale_days() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &TableOptions {
+                stale_days: HashSet::from([day!(2)]),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) ⚠️ | `30ms` | `40ms` |"));
+        assert!(s.contains("**Total: 190.00ms**\n\n⚠️ _Code or input changed"));
+    }
 }
@@ -0,0 +1,176 @@
+/// Module that updates the readme with a table of accepted puzzle parts.
+/// The table has the same format as the one generated by the `advent-readme-stars` action,
+/// but is generated from `data/progress.json` instead of the Advent of Code API.
+use std::{fs, io};
+
+use crate::template::{aoc_cli, progress::Progress};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+fn construct_table(progress: &Progress, year: Option<u16>) -> Vec<String> {
+    let header = match year {
+        Some(year) => format!("## {year} Results"),
+        None => "## Results".into(),
+    };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, stars) in &progress.stars {
+        let day = day.into_inner();
+        let label = match year {
+            Some(year) => format!("[Day {day}](https://adventofcode.com/{year}/day/{day})"),
+            None => format!("Day {day}"),
+        };
+        let star = |accepted: bool| if accepted { " ⭐ |" } else { " |" };
+        lines.push(format!("| {label} |{}{}", star(stars[0]), star(stars[1])));
+    }
+
+    lines
+}
+
+/// Replace the marker and the table that follows it, if any, with an updated table.
+fn update_content(s: &mut String, progress: &Progress, year: Option<u16>) -> Result<(), Error> {
+    if s.matches(MARKER).count() > 1 {
+        return Err(Error::Parser(
+            "too many occurences of marker in README.".into(),
+        ));
+    }
+
+    let lines: Vec<&str> = s.split_inclusive('\n').collect();
+    let is_table = |i: usize| lines.get(i).is_some_and(|l| l.starts_with('|'));
+
+    let start = lines
+        .iter()
+        .position(|l| l.trim_end() == MARKER)
+        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+
+    // a previously generated table consists of a `Results` heading, a blank line and table rows.
+    let mut end = start + 1;
+    if lines
+        .get(end)
+        .is_some_and(|l| l.starts_with("## ") && l.trim_end().ends_with("Results"))
+    {
+        end += 1;
+        if lines.get(end).is_some_and(|l| l.trim().is_empty()) && is_table(end + 1) {
+            end += 1;
+        }
+        while is_table(end) {
+            end += 1;
+        }
+    }
+
+    let mut table = construct_table(progress, year).join("\n");
+    if lines[end - 1].ends_with('\n') {
+        table.push('\n');
+    }
+
+    let mut content: String = lines[..start].concat();
+    content.push_str(&table);
+    content.push_str(&lines[end..].concat());
+    *s = content;
+
+    Ok(())
+}
+
+pub fn update(progress: &Progress) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress, aoc_cli::get_year())?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::{day, template::progress::Progress};
+
+    fn get_mock_progress() -> Progress {
+        let mut progress = Progress::default();
+        progress.set(day!(1), 1, true);
+        progress.set(day!(1), 2, true);
+        progress.set(day!(2), 1, true);
+        progress
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_too_many_markers_present() {
+        let mut s = format!("{MARKER}\n{MARKER}\n");
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n\n{MARKER}\n\nbar\n");
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+        let expected = [
+            "foo",
+            "",
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ | |",
+            "",
+            "bar",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_existing_stars() {
+        let mut s = format!("foo\n{MARKER}\n\nbar\n");
+        update_content(&mut s, &Progress::default(), None).unwrap();
+        update_content(&mut s, &get_mock_progress(), Some(2024)).unwrap();
+
+        let mut progress = get_mock_progress();
+        progress.set(day!(2), 2, true);
+        update_content(&mut s, &progress, Some(2024)).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 1);
+        assert_eq!(s.matches("Results").count(), 1);
+        assert_eq!(s.matches("⭐").count(), 4);
+        assert!(s.starts_with("foo\n"));
+        assert!(s.ends_with("| ⭐ | ⭐ |\n\nbar\n"));
+    }
+
+    #[test]
+    fn handles_marker_at_end_of_file() {
+        let mut s = format!("foo\n{MARKER}");
+        update_content(&mut s, &get_mock_progress(), None).unwrap();
+        update_content(&mut s, &get_mock_progress(), None).unwrap();
+        assert!(s.ends_with("| Day 2 | ⭐ | |"));
+        assert_eq!(s.matches("## Results").count(), 1);
+    }
+}
@@ -0,0 +1,368 @@
+/// Module that writes machine-readable reports (JUnit XML, TAP) for multi-day runs.
+/// Every part of every day becomes a single test case.
+use std::{error::Error, fmt::Display, fs, io, str::FromStr};
+
+use crate::template::Day;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    JUnit,
+    Tap,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::JUnit => f.write_str("JUnit"),
+            Format::Tap => f.write_str("TAP"),
+        }
+    }
+}
+
+/// A report that should be written after a run, parsed from `<format>=<path>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub format: Format,
+    pub path: String,
+}
+
+impl FromStr for Report {
+    type Err = ReportFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (format, path) = s.split_once('=').ok_or(ReportFromStrError)?;
+
+        let format = match format {
+            "junit" => Format::JUnit,
+            "tap" => Format::Tap,
+            _ => return Err(ReportFromStrError),
+        };
+
+        if path.is_empty() {
+            return Err(ReportFromStrError);
+        }
+
+        Ok(Self {
+            format,
+            path: path.into(),
+        })
+    }
+}
+
+/// An error which can be returned when parsing a [`Report`].
+#[derive(Debug)]
+pub struct ReportFromStrError;
+
+impl Error for ReportFromStrError {}
+
+impl Display for ReportFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a report in the format `junit=<path>` or `tap=<path>`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    Passed,
+    Skipped(String),
+    Failed(String),
+    LimitExceeded(String),
+}
+
+/// The outcome of running a single part of a day.
+#[derive(Clone, Debug)]
+pub struct TestCase {
+    pub day: Day,
+    pub part: u8,
+    pub status: Status,
+    pub nanos: Option<f64>,
+    /// The input file the case ran against, if the day has more than one.
+    pub input: Option<String>,
+}
+
+impl TestCase {
+    fn name(&self) -> String {
+        format!("Day {} {}", self.day, self.part_name())
+    }
+
+    fn part_name(&self) -> String {
+        match &self.input {
+            Some(input) => format!("Part {} ({input})", self.part),
+            None => format!("Part {}", self.part),
+        }
+    }
+}
+
+impl Report {
+    /// Write the test cases to `self.path` in the report's format.
+    pub fn write(&self, cases: &[TestCase]) -> Result<(), io::Error> {
+        let contents = match self.format {
+            Format::JUnit => format_junit(cases),
+            Format::Tap => format_tap(cases),
+        };
+        fs::write(&self.path, contents)
+    }
+}
+
+/// Write all requested reports, printing the outcome for each of them.
+pub fn write_all(reports: &[Report], cases: &[TestCase]) {
+    for report in reports {
+        match report.write(cases) {
+            Ok(()) => println!("Wrote {} report to \"{}\".", report.format, report.path),
+            Err(e) => eprintln!(
+                "Failed to write {} report to \"{}\": {e}",
+                report.format, report.path
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn format_junit(cases: &[TestCase]) -> String {
+    let (failures, skipped) = count_unsuccessful(cases.iter());
+
+    let mut lines: Vec<String> = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.into(),
+        format!(
+            r#"<testsuites name="advent_of_code" tests="{}" failures="{failures}" skipped="{skipped}" time="{:.9}">"#,
+            cases.len(),
+            total_seconds(cases.iter())
+        ),
+    ];
+
+    let mut days: Vec<Day> = cases.iter().map(|c| c.day).collect();
+    days.dedup();
+
+    for day in days {
+        let day_cases: Vec<&TestCase> = cases.iter().filter(|c| c.day == day).collect();
+        let (day_failures, day_skipped) = count_unsuccessful(day_cases.iter().copied());
+
+        lines.push(format!(
+            r#"  <testsuite name="Day {day}" tests="{}" failures="{day_failures}" skipped="{day_skipped}" time="{:.9}">"#,
+            day_cases.len(),
+            total_seconds(day_cases.iter().copied())
+        ));
+
+        for case in day_cases {
+            let open = format!(
+                r#"    <testcase classname="day_{day}" name="{}" time="{:.9}""#,
+                escape_xml(&case.part_name()),
+                case.nanos.unwrap_or_default() / 1_000_000_000_f64
+            );
+
+            match &case.status {
+                Status::Passed => lines.push(format!("{open} />")),
+                Status::Skipped(message) => {
+                    lines.push(format!("{open}>"));
+                    lines.push(format!(
+                        r#"      <skipped message="{}" />"#,
+                        escape_xml(message)
+                    ));
+                    lines.push("    </testcase>".into());
+                }
+                Status::Failed(message) => {
+                    let summary = message.lines().next().unwrap_or_default();
+                    lines.push(format!("{open}>"));
+                    lines.push(format!(
+                        r#"      <failure message="{}">{}</failure>"#,
+                        escape_xml(summary),
+                        escape_xml(message)
+                    ));
+                    lines.push("    </testcase>".into());
+                }
+                Status::LimitExceeded(message) => {
+                    lines.push(format!("{open}>"));
+                    lines.push(format!(
+                        r#"      <failure type="limit-exceeded" message="{}" />"#,
+                        escape_xml(message)
+                    ));
+                    lines.push("    </testcase>".into());
+                }
+            }
+        }
+
+        lines.push("  </testsuite>".into());
+    }
+
+    lines.push("</testsuites>".into());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn format_tap(cases: &[TestCase]) -> String {
+    let mut lines: Vec<String> = vec!["TAP version 13".into(), format!("1..{}", cases.len())];
+
+    for (i, case) in cases.iter().enumerate() {
+        let number = i + 1;
+        let time = case
+            .nanos
+            .map(|n| format!(" # time={:.3}ms", n / 1_000_000_f64))
+            .unwrap_or_default();
+
+        match &case.status {
+            Status::Passed => lines.push(format!("ok {number} - {}{time}", case.name())),
+            Status::Skipped(message) => {
+                lines.push(format!("ok {number} - {} # SKIP {message}", case.name()));
+            }
+            Status::Failed(message) => {
+                lines.push(format!("not ok {number} - {}{time}", case.name()));
+                lines.push("  ---".into());
+                lines.push("  message: |".into());
+                lines.extend(message.lines().map(|l| format!("    {l}")));
+                lines.push("  ...".into());
+            }
+            Status::LimitExceeded(message) => {
+                lines.push(format!(
+                    "not ok {number} - {} # limit exceeded: {message}",
+                    case.name()
+                ));
+            }
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Count failed and skipped test cases.
+fn count_unsuccessful<'a>(cases: impl Iterator<Item = &'a TestCase>) -> (usize, usize) {
+    cases.fold((0, 0), |(failed, skipped), c| match c.status {
+        Status::Passed => (failed, skipped),
+        Status::Skipped(_) => (failed, skipped + 1),
+        Status::Failed(_) | Status::LimitExceeded(_) => (failed + 1, skipped),
+    })
+}
+
+fn total_seconds<'a>(cases: impl Iterator<Item = &'a TestCase>) -> f64 {
+    cases.filter_map(|c| c.nanos).fold(0_f64, |acc, n| acc + n) / 1_000_000_000_f64
+}
+
+pub fn escape_xml(s: &str) -> String {
+    s.chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .fold(String::with_capacity(s.len()), |mut acc, c| {
+            match c {
+                '&' => acc.push_str("&amp;"),
+                '<' => acc.push_str("&lt;"),
+                '>' => acc.push_str("&gt;"),
+                '"' => acc.push_str("&quot;"),
+                '\'' => acc.push_str("&apos;"),
+                c => acc.push(c),
+            }
+            acc
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{format_junit, format_tap, Format, Report, Status, TestCase};
+    use crate::day;
+
+    fn get_mock_cases() -> Vec<TestCase> {
+        vec![
+            TestCase {
+                day: day!(1),
+                part: 1,
+                status: Status::Passed,
+                nanos: Some(1_500_000_f64),
+                input: None,
+            },
+            TestCase {
+                day: day!(1),
+                part: 2,
+                status: Status::Failed("assertion `left == right` failed\n  left: <1>".into()),
+                nanos: None,
+                input: None,
+            },
+            TestCase {
+                day: day!(2),
+                part: 1,
+                status: Status::Skipped("not solved".into()),
+                nanos: None,
+                input: None,
+            },
+            TestCase {
+                day: day!(3),
+                part: 1,
+                status: Status::LimitExceeded("CPU time limit of 1s exceeded.".into()),
+                nanos: None,
+                input: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_reports() {
+        let report = Report::from_str("junit=target/report.xml").unwrap();
+        assert_eq!(report.format, Format::JUnit);
+        assert_eq!(report.path, "target/report.xml");
+
+        let report = Report::from_str("tap=report.tap").unwrap();
+        assert_eq!(report.format, Format::Tap);
+
+        assert!(Report::from_str("xml=report.xml").is_err());
+        assert!(Report::from_str("junit=").is_err());
+        assert!(Report::from_str("junit").is_err());
+    }
+
+    #[test]
+    fn formats_junit() {
+        let xml = format_junit(&get_mock_cases());
+        assert!(
+            xml.contains(r#"<testsuites name="advent_of_code" tests="4" failures="2" skipped="1""#)
+        );
+        assert!(xml.contains(r#"<testsuite name="Day 01" tests="2" failures="1" skipped="0""#));
+        assert!(xml.contains(r#"<testcase classname="day_01" name="Part 1" time="0.001500000" />"#));
+        assert!(xml.contains(r#"<failure message="assertion `left == right` failed">"#));
+        assert!(xml.contains("  left: &lt;1&gt;</failure>"));
+        assert!(xml.contains(r#"<skipped message="not solved" />"#));
+        assert!(xml.contains(
+            r#"<failure type="limit-exceeded" message="CPU time limit of 1s exceeded." />"#
+        ));
+    }
+
+    #[test]
+    fn formats_tap() {
+        let tap = format_tap(&get_mock_cases());
+        let expected = [
+            "TAP version 13",
+            "1..4",
+            "ok 1 - Day 01 Part 1 # time=1.500ms",
+            "not ok 2 - Day 01 Part 2",
+            "  ---",
+            "  message: |",
+            "    assertion `left == right` failed",
+            "      left: <1>",
+            "  ...",
+            "ok 3 - Day 02 Part 1 # SKIP not solved",
+            "not ok 4 - Day 03 Part 1 # limit exceeded: CPU time limit of 1s exceeded.",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(tap, expected);
+    }
+
+    #[test]
+    fn names_cases_by_input() {
+        let cases = [1, 2].map(|part| TestCase {
+            day: day!(1),
+            part,
+            status: Status::Passed,
+            nanos: Some(1_000_000_f64),
+            input: Some("01-alice.txt".into()),
+        });
+
+        let xml = format_junit(&cases);
+        assert!(xml.contains(r#"<testcase classname="day_01" name="Part 2 (01-alice.txt)""#));
+
+        let tap = format_tap(&cases);
+        assert!(tap.contains("ok 1 - Day 01 Part 1 (01-alice.txt) # time=1.000ms"));
+    }
+}
@@ -1,48 +1,283 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    io,
+    path::{Path, PathBuf},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{interrupt, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
-    timings::{Timing, Timings},
+    all_days, answers, input_variants,
+    limits::Limits,
+    profile::Profile,
+    report::{self, Report, TestCase},
+    table::format_table,
+    timings::{format_nanos, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    profile: &Profile,
+    is_timed: bool,
+    limits: &Limits,
+    reports: &[Report],
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut test_cases: Vec<TestCase> = vec![];
 
     let mut need_space = false;
 
+    interrupt::install_handler("Stopping after the current day.");
+
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let limits = limits.for_day(day);
+
+        // timed runs bench every input of a day, e.g. `01.txt` and `01-alice.txt`.
+        let variants = if is_timed {
+            input_variants(day)
+        } else {
+            vec![]
+        };
+        let inputs: Vec<Option<PathBuf>> = if variants.len() > 1
+            || variants
+                .first()
+                .is_some_and(|path| !is_default_input(path, day))
+        {
+            variants.into_iter().map(Some).collect()
+        } else {
+            vec![None]
+        };
+
+        let mut executions = Vec::with_capacity(inputs.len());
+
+        for input in &inputs {
+            if let Some(path) = input {
+                println!("{ANSI_ITALIC}Input {}{ANSI_RESET}", input_name(path));
             }
-            need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            let execution =
+                child_commands::run_solution(day, is_timed, profile, &limits, input.as_deref())
+                    .unwrap();
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            if let Some(kind) = execution.limit_exceeded {
+                println!("Limit exceeded: {}", limits.describe(kind));
+            }
+
+            executions.push(execution);
+
+            if interrupt::is_interrupted() {
+                break;
+            }
+        }
 
-            if output.is_empty() {
-                println!("Not solved.");
+        // the last execution of an interrupted day only ran the parts that finished before Ctrl-C.
+        let interrupted = interrupt::is_interrupted();
+
+        // NOTE: known answers are for the default input.
+        let known = answers::read_known(day);
+        let is_default = |input: &Option<PathBuf>| {
+            input
+                .as_deref()
+                .is_none_or(|path| is_default_input(path, day))
+        };
+
+        for (i, (input, execution)) in inputs.iter().zip(&executions).enumerate() {
+            let mut cases = child_commands::parse_test_cases(execution, day, &limits);
+            if let Some(known) = known.as_ref().filter(|_| is_default(input)) {
+                let answers = child_commands::parse_answers(&execution.stdout);
+                child_commands::fail_wrong_answers(
+                    &mut cases,
+                    answers::mismatches(known, &answers),
+                );
+            }
+            if interrupted && i == executions.len() - 1 {
+                child_commands::skip_interrupted(&mut cases);
+            }
+            test_cases.extend(cases.into_iter().map(|case| TestCase {
+                input: input.as_deref().map(input_name),
+                ..case
+            }));
+        }
+
+        if executions.iter().all(|e| e.stdout.is_empty()) {
+            println!("Not solved.");
+        } else {
+            let per_input: Vec<Timing> = executions
+                .iter()
+                .map(|e| child_commands::parse_exec_time(&e.stdout, day))
+                .collect();
+
+            let mut val = if per_input.len() > 1 {
+                let aggregate = Timing::aggregate(day, &per_input);
+                print_input_summary(&inputs, &per_input, &aggregate);
+                aggregate
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                per_input.into_iter().next().unwrap()
+            };
+
+            val.profile = Some(profile.to_string());
+            val.interrupted = interrupted;
+            let default_execution = inputs
+                .iter()
+                .zip(&executions)
+                .find(|(input, _)| is_default(input))
+                .map(|(_, execution)| execution);
+            if let (Some(known), Some(execution), false) = (&known, default_execution, interrupted)
+            {
+                val.verified = answers::verify_against(
+                    known,
+                    &child_commands::parse_answers(&execution.stdout),
+                );
             }
-        });
+            timings.push(val);
+        }
+
+        if interrupted {
+            println!("\n{ANSI_ITALIC}Interrupted, skipping remaining days.{ANSI_RESET}");
+            break;
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let timings = is_timed.then(|| {
+        let timings = Timings {
+            data: timings,
+            machines: vec![],
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        for timing in timings.data.iter().filter(|t| t.interrupted) {
+            println!(
+                "{ANSI_ITALIC}Day {} was interrupted, its timings only cover the finished parts and are not stored.{ANSI_RESET}",
+                timing.day
+            );
+        }
+        timings
+    });
+
+    if !reports.is_empty() {
+        println!();
+        report::write_all(reports, &test_cases);
+    }
+
+    timings
+}
+
+/// Run a day against each of its inputs, e.g. `01.txt` and `01@alice.txt`, and print their answers side by side.
+pub fn run_inputs(day: Day, profile: &Profile, limits: &Limits) {
+    let limits = limits.for_day(day);
+    let inputs = input_variants(day);
+
+    if inputs.is_empty() {
+        eprintln!("No inputs found for day {day}.");
+        return;
+    }
+
+    interrupt::install_handler("Stopping after the current input.");
+
+    let mut rows = vec![];
+
+    for path in &inputs {
+        println!("{ANSI_ITALIC}Input {}{ANSI_RESET}", input_name(path));
+
+        let execution =
+            child_commands::run_solution(day, false, profile, &limits, Some(path)).unwrap();
+
+        if let Some(kind) = execution.limit_exceeded {
+            println!("Limit exceeded: {}", limits.describe(kind));
+        }
+
+        rows.push((
+            input_name(path),
+            child_commands::parse_answers(&execution.stdout),
+        ));
+
+        if interrupt::is_interrupted() {
+            break;
+        }
+    }
+
+    println!();
+    for line in format_answer_table(&rows) {
+        println!("{line}");
+    }
+}
+
+/// Format the answers of every input as a table. Missing answers are shown as `-`.
+fn format_answer_table(rows: &[(String, [Option<String>; 2])]) -> Vec<String> {
+    let mut cells: Vec<[&str; 3]> = vec![["Input", "Part 1", "Part 2"]];
+    cells.extend(rows.iter().map(|(name, [part_1, part_2])| {
+        [
+            name.as_str(),
+            part_1.as_deref().unwrap_or("-"),
+            part_2.as_deref().unwrap_or("-"),
+        ]
+    }));
+
+    format_table(&cells)
+}
+
+fn input_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+fn is_default_input(path: &Path, day: Day) -> bool {
+    input_name(path) == format!("{day}.txt")
+}
+
+/// Print the durations of every input of a day, followed by their mean and max.
+fn print_input_summary(inputs: &[Option<PathBuf>], per_input: &[Timing], aggregate: &Timing) {
+    let cell = |nanos: Option<f64>| nanos.map_or_else(|| "-".into(), format_nanos);
+
+    let mut rows: Vec<[String; 3]> = inputs
+        .iter()
+        .zip(per_input)
+        .map(|(input, timing)| {
+            [
+                input.as_deref().map(input_name).unwrap_or_default(),
+                cell(timing.part_1_nanos),
+                cell(timing.part_2_nanos),
+            ]
+        })
+        .collect();
+
+    rows.push([
+        "mean".into(),
+        cell(aggregate.part_1_nanos),
+        cell(aggregate.part_2_nanos),
+    ]);
+    rows.push([
+        "max".into(),
+        cell(aggregate.part_1_max_nanos),
+        cell(aggregate.part_2_max_nanos),
+    ]);
+
+    let width = rows
+        .iter()
+        .map(|row| row[0].len())
+        .max()
+        .unwrap_or_default();
+
+    println!();
+    println!(
+        "{ANSI_BOLD}{:width$}  {:>10}  {:>10}{ANSI_RESET}",
+        "Input", "Part 1", "Part 2"
+    );
+    for [name, part_1, part_2] in rows {
+        println!("{name:width$}  {part_1:>10}  {part_2:>10}");
     }
 }
 
@@ -68,7 +303,14 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::timings::parse_duration;
+    use crate::template::{
+        limits::{LimitKind, Limits},
+        profile::Profile,
+        report::{Status, TestCase},
+        runner::PEAK_MEMORY_PREFIX,
+        Day, ANSI_BOLD, ANSI_RESET, INPUT_FILE_ENV,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,30 +318,58 @@ pub mod child_commands {
         thread,
     };
 
+    /// Captured output of a solution bin.
+    pub struct Execution {
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+        pub success: bool,
+        pub limit_exceeded: Option<LimitKind>,
+    }
+
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        profile: &Profile,
+        limits: &Limits,
+        input: Option<&Path>,
+    ) -> Result<Execution, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(Execution {
+                stdout: vec![],
+                stderr: vec![],
+                success: true,
+                limit_exceeded: None,
+            });
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            day.to_string(),
+        ];
+        args.extend(profile.cargo_args());
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--".into());
+            args.push("--time".into());
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing stdout and stderr lines.
+
+        let mut cmd = Command::new("cargo");
+        profile.apply_env(&mut cmd);
+        limits.apply_env(&mut cmd);
 
-        let mut cmd = Command::new("cargo")
+        if let Some(input) = input {
+            cmd.env(INPUT_FILE_ENV, input);
+        }
+
+        let mut cmd = cmd
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -111,9 +381,14 @@ pub mod child_commands {
         let mut output = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            stderr
+                .lines()
+                .map(|line| {
+                    let line = line.unwrap();
+                    eprintln!("{line}");
+                    line
+                })
+                .collect::<Vec<_>>()
         });
 
         for line in stdout.lines() {
@@ -122,18 +397,119 @@ pub mod child_commands {
             output.push(line);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let errors = thread.join().unwrap();
+        let status = cmd.wait()?;
 
-        Ok(output)
+        let limit_exceeded = if status.success() {
+            None
+        } else {
+            limits.exceeded(&errors)
+        };
+
+        Ok(Execution {
+            stdout: output,
+            stderr: errors,
+            success: status.success(),
+            limit_exceeded,
+        })
+    }
+
+    /// Derive one test case per part from the output of a solution bin.
+    pub fn parse_test_cases(execution: &Execution, day: Day, limits: &Limits) -> Vec<TestCase> {
+        if execution.stdout.is_empty() && execution.success {
+            return [1, 2]
+                .map(|part| TestCase {
+                    day,
+                    part,
+                    status: Status::Skipped("not solved".into()),
+                    nanos: None,
+                    input: None,
+                })
+                .to_vec();
+        }
+
+        let mut has_failed = false;
+
+        [1, 2]
+            .map(|part| {
+                let prefix = format!("Part {part}:");
+                // intermediate results are overwritten with a carriage return, keep the final one.
+                let line = execution
+                    .stdout
+                    .iter()
+                    .filter_map(|l| l.rsplit('\r').next())
+                    .find(|l| l.starts_with(&prefix));
+
+                let (status, nanos) = match line.map(|l| (l, parse_time(l))) {
+                    Some((l, _)) if l.contains('✖') => {
+                        (Status::Skipped("no result returned".into()), None)
+                    }
+                    Some((_, Some((_, nanos)))) => (Status::Passed, Some(nanos)),
+                    _ if execution.success => (Status::Skipped("not implemented".into()), None),
+                    _ if has_failed => (Status::Skipped("not run".into()), None),
+                    _ => {
+                        has_failed = true;
+                        let status = match execution.limit_exceeded {
+                            Some(kind) => Status::LimitExceeded(limits.describe(kind)),
+                            None => Status::Failed(failure_message(&execution.stderr)),
+                        };
+                        (status, None)
+                    }
+                };
+
+                TestCase {
+                    day,
+                    part,
+                    status,
+                    nanos,
+                    input: None,
+                }
+            })
+            .to_vec()
+    }
+
+    /// Report parts that ran but returned a wrong answer as failed, with the mismatch as the message.
+    pub fn fail_wrong_answers(cases: &mut [TestCase], mismatches: [Option<String>; 2]) {
+        for (case, mismatch) in cases.iter_mut().zip(mismatches) {
+            if let (Status::Passed, Some(mismatch)) = (&case.status, mismatch) {
+                case.status = Status::Failed(mismatch);
+            }
+        }
+    }
+
+    /// Report parts that did not finish before Ctrl-C as skipped.
+    pub fn skip_interrupted(cases: &mut [TestCase]) {
+        for case in cases {
+            if matches!(case.status, Status::Skipped(_)) {
+                case.status = Status::Skipped("interrupted".into());
+            }
+        }
+    }
+
+    /// Extract the panic message from stderr, falling back to the full stderr output.
+    fn failure_message(stderr: &[String]) -> String {
+        let panic_start = stderr.iter().position(|l| l.contains("panicked at"));
+
+        let lines: Vec<&str> = match panic_start {
+            Some(start) => stderr[start..]
+                .iter()
+                .map(String::as_str)
+                .take_while(|l| !l.starts_with("note: ") && !l.starts_with("stack backtrace:"))
+                .collect(),
+            None => stderr.iter().map(String::as_str).collect(),
+        };
+
+        if lines.is_empty() {
+            "solution exited with a non-zero status.".into()
+        } else {
+            lines.join("\n")
+        }
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+            ..Default::default()
         };
 
         output
@@ -149,42 +525,68 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_samples(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, samples)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_nanos = Some(nanos);
+                    timings.part_1_samples = samples;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_nanos = Some(nanos);
+                    timings.part_2_samples = samples;
                 }
 
                 timings.total_nanos += nanos;
             });
 
+        timings.peak_memory_kb = output.iter().find_map(|l| {
+            l.strip_prefix(PEAK_MEMORY_PREFIX)?
+                .strip_suffix(" KiB")?
+                .parse()
+                .ok()
+        });
+
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Extract the answers of both parts. Multi-line answers can not be extracted.
+    pub fn parse_answers(output: &[String]) -> [Option<String>; 2] {
+        [1, 2].map(|part| {
+            let prefix = format!("Part {part}: ");
+            output
+                .iter()
+                .filter_map(|l| l.rsplit('\r').next())
+                .find_map(|l| l.strip_prefix(&prefix))
+                .and_then(|l| l.rsplit_once(" (").map(|(answer, _)| answer))
+                .map(|answer| answer.replace(ANSI_BOLD, "").replace(ANSI_RESET, ""))
+                .filter(|answer| !answer.starts_with('▼') && !answer.starts_with('✖'))
+        })
+    }
+
+    fn parse_samples(line: &str) -> Option<u64> {
+        line.split(" samples)")
+            .next()?
+            .rsplit('@')
+            .next()?
+            .trim()
+            .parse()
+            .ok()
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
-            .trim();
+            .trim()
+            .trim_end_matches(')');
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_duration(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
@@ -205,9 +607,19 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{
+            fail_wrong_answers, parse_answers, parse_exec_time, parse_test_cases, skip_interrupted,
+            Execution,
+        };
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{
+                answers,
+                limits::{LimitKind, Limits},
+                report::Status,
+            },
+        };
 
         #[test]
         fn parses_execution_times() {
@@ -222,6 +634,40 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1_nanos.unwrap(), 74.13_f64);
+            assert_approx_eq!(res.part_2_nanos.unwrap(), 74130000_f64);
+            assert_eq!(res.part_1_samples, Some(100000));
+            assert_eq!(res.part_2_samples, Some(99999));
+            assert_eq!(res.peak_memory_kb, None);
+        }
+
+        #[test]
+        fn parses_peak_memory() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Peak memory: 2048 KiB".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.peak_memory_kb, Some(2048));
+        }
+
+        #[test]
+        fn parses_answers() {
+            let answers = parse_answers(&[
+                "Part 1: 42 > benching\rPart 1: \x1b[1m42\x1b[0m (74.13ns @ 100000 samples)".into(),
+                "Part 2: ▼  (1.2ms)".into(),
+                "a multi-line".into(),
+                "answer".into(),
+            ]);
+            assert_eq!(answers, [Some("42".into()), None]);
+
+            let answers = parse_answers(&[
+                "Part 1: (a) b (1.2ms)".into(),
+                "Part 2: ✖             ".into(),
+            ]);
+            assert_eq!(answers, [Some("(a) b".into()), None]);
         }
 
         #[test]
@@ -253,5 +699,171 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_test_cases() {
+            let res = parse_test_cases(
+                &Execution {
+                    stdout: vec![
+                        "Part 1: 0 > benching\rPart 1: 0 (74.13ns @ 100000 samples)".into(),
+                        "Part 2: ✖\rPart 2: ✖             ".into(),
+                    ],
+                    stderr: vec![],
+                    success: true,
+                    limit_exceeded: None,
+                },
+                day!(1),
+                &Limits::default(),
+            );
+            assert_eq!(res.len(), 2);
+            assert_eq!(res[0].status, Status::Passed);
+            assert_approx_eq!(res[0].nanos.unwrap(), 74.13_f64);
+            assert_eq!(res[1].status, Status::Skipped("no result returned".into()));
+        }
+
+        #[test]
+        fn fails_test_cases_with_wrong_answers() {
+            let execution = Execution {
+                stdout: vec!["Part 1: 12 (1.5ms)".into(), "Part 2: 31 (2.5ms)".into()],
+                stderr: vec![],
+                success: true,
+                limit_exceeded: None,
+            };
+            let mut res = parse_test_cases(&execution, day!(1), &Limits::default());
+
+            let known = [Some("11".into()), Some("31".into())];
+            fail_wrong_answers(
+                &mut res,
+                answers::mismatches(&known, &parse_answers(&execution.stdout)),
+            );
+            assert_eq!(res[0].status, Status::Failed("expected 11, got 12".into()));
+            assert_eq!(res[1].status, Status::Passed);
+        }
+
+        #[test]
+        fn skips_interrupted_test_cases() {
+            let mut res = parse_test_cases(
+                &Execution {
+                    stdout: vec!["Part 1: 42 (1.5ms @ 12 samples)".into()],
+                    stderr: vec![],
+                    success: true,
+                    limit_exceeded: None,
+                },
+                day!(1),
+                &Limits::default(),
+            );
+            skip_interrupted(&mut res);
+
+            assert_eq!(res[0].status, Status::Passed);
+            assert_approx_eq!(res[0].nanos.unwrap(), 1_500_000_f64);
+            assert_eq!(res[1].status, Status::Skipped("interrupted".into()));
+        }
+
+        #[test]
+        fn parses_untimed_test_cases() {
+            let res = parse_test_cases(
+                &Execution {
+                    stdout: vec!["Part 1: 42\rPart 1: 42 (1.5ms)".into()],
+                    stderr: vec![],
+                    success: true,
+                    limit_exceeded: None,
+                },
+                day!(1),
+                &Limits::default(),
+            );
+            assert_eq!(res[0].status, Status::Passed);
+            assert_approx_eq!(res[0].nanos.unwrap(), 1_500_000_f64);
+            assert_eq!(res[1].status, Status::Skipped("not implemented".into()));
+        }
+
+        #[test]
+        fn parses_failed_test_cases() {
+            let res = parse_test_cases(
+                &Execution {
+                    stdout: vec!["Part 1: ".into()],
+                    stderr: vec![
+                        "thread 'main' panicked at src/bin/01.rs:10:5:".into(),
+                        "attempt to subtract with overflow".into(),
+                        "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace".into(),
+                    ],
+                    success: false,
+                    limit_exceeded: None,
+                },
+                day!(1),
+                &Limits::default(),
+            );
+            assert_eq!(
+                res[0].status,
+                Status::Failed(
+                    "thread 'main' panicked at src/bin/01.rs:10:5:\nattempt to subtract with overflow"
+                        .into()
+                )
+            );
+            assert_eq!(res[1].status, Status::Skipped("not run".into()));
+        }
+
+        #[test]
+        fn parses_unsolved_test_cases() {
+            let res = parse_test_cases(
+                &Execution {
+                    stdout: vec![],
+                    stderr: vec![],
+                    success: true,
+                    limit_exceeded: None,
+                },
+                day!(1),
+                &Limits::default(),
+            );
+            assert_eq!(res[0].status, Status::Skipped("not solved".into()));
+            assert_eq!(res[1].status, Status::Skipped("not solved".into()));
+        }
+
+        #[test]
+        fn parses_limit_exceeded_test_cases() {
+            let limits = Limits {
+                memory_mb: Some(64),
+                cpu_secs: None,
+            };
+            let res = parse_test_cases(
+                &Execution {
+                    stdout: vec!["Part 1: ".into()],
+                    stderr: vec!["memory allocation of 1073741824 bytes failed".into()],
+                    success: false,
+                    limit_exceeded: Some(LimitKind::Memory),
+                },
+                day!(1),
+                &limits,
+            );
+            assert_eq!(
+                res[0].status,
+                Status::LimitExceeded("memory limit of 64MB exceeded.".into())
+            );
+            assert_eq!(res[1].status, Status::Skipped("not run".into()));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_answer_table;
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn formats_answers_side_by_side() {
+        let rows = [
+            ("01.txt".to_string(), [Some("11".into()), Some("31".into())]),
+            ("01@alice.txt".to_string(), [Some("1234567".into()), None]),
+        ];
+
+        assert_eq!(
+            format_answer_table(&rows),
+            [
+                format!("{ANSI_BOLD}Input         Part 1   Part 2{ANSI_RESET}"),
+                "01.txt        11       31".to_string(),
+                "01@alice.txt  1234567  -".to_string(),
+            ]
+        );
     }
 }
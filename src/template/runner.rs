@@ -2,14 +2,28 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
+use crate::template::commands::stars;
+use crate::template::cooldown::{self, Cooldowns};
+use crate::template::provider::{self, ProviderError};
+use crate::template::submissions::{self, Outcome, Submission};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{interrupt, Day, ANSI_ITALIC, ANSI_RESET};
+
+/// Prefix of the line that reports the peak memory usage of a timed run.
+pub const PEAK_MEMORY_PREFIX: &str = "Peak memory: ";
+
+/// Submissions that are rate limited are retried at most this often with `--wait`.
+const MAX_SUBMIT_ATTEMPTS: usize = 3;
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    // a previous part was interrupted while benching, skip the remaining parts.
+    if interrupt::is_interrupted() {
+        return;
+    }
+
     let part_str = format!("Part {part}");
 
     let (result, duration, samples) =
@@ -17,11 +31,65 @@ pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, da
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
+    if interrupt::is_interrupted() {
+        println!("Benchmark interrupted, showing statistics for {samples} samples.");
+    }
+
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Print the peak resident memory of the process when benching, so that `cargo time` can record it.
+pub fn print_peak_memory() {
+    if !env::args().any(|x| x == "--time") {
+        return;
+    }
+
+    if let Some(kb) = peak_memory_kb() {
+        println!("{PEAK_MEMORY_PREFIX}{kb} KiB");
+    }
+}
+
+/// On linux, `ru_maxrss` carries over across `exec`, which would include the memory of the
+/// `cargo run` process that execs the solution. `VmHWM` is reset on `exec`, so prefer it.
+#[cfg(target_os = "linux")]
+fn peak_memory_kb() -> Option<u64> {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()?
+        .lines()
+        .find_map(|l| l.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix(" kB")?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn peak_memory_kb() -> Option<u64> {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::zeroed();
+    // SAFETY: `usage` is a valid pointer to a `rusage` struct that `getrusage` fills.
+    if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    // SAFETY: `getrusage` succeeded, so the struct is initialized.
+    let max_rss = unsafe { usage.assume_init() }.ru_maxrss;
+
+    // `ru_maxrss` is reported in bytes on macOS and in KiB everywhere else.
+    let kb = if cfg!(target_os = "macos") {
+        max_rss / 1024
+    } else {
+        max_rss
+    };
+    u64::try_from(kb).ok()
+}
+
+#[cfg(not(unix))]
+fn peak_memory_kb() -> Option<u64> {
+    None
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
@@ -50,7 +118,10 @@ fn run_timed<I: Copy, T>(
     (result, run.0, run.1)
 }
 
+/// Bench a solution part. Pressing Ctrl-C stops the bench and returns the statistics gathered so far.
 fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+    interrupt::install_handler("Stopping benchmark.");
+
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -62,15 +133,22 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
+        if interrupt::is_interrupted() {
+            break;
+        }
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
+    if timers.is_empty() {
+        return (*base_time, 1);
+    }
+
     (
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+        timers.len() as u128,
     )
 }
 
@@ -127,12 +205,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is available.
+///  3. earlier submissions do not rule out the answer.
+///  4. the day is not in a cooldown, or `--wait` was passed to wait for it to pass.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<String, ProviderError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,11 +235,87 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answer = result.to_string();
+
+    if let Err(refusal) = submissions::check(&submissions::read_for_part(day, part), &answer) {
+        eprintln!("Not submitting `{answer}`: {refusal}");
+        return None;
+    }
+
+    let provider = match provider::from_env(None) {
+        Ok(provider) => provider,
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            return Some(Err(e));
+        }
+    };
+
+    let wait = args.contains(&"--wait".into());
+
+    for _ in 0..MAX_SUBMIT_ATTEMPTS {
+        if let Some(remaining) = Cooldowns::read_from_file().remaining(day, cooldown::now()) {
+            if !wait {
+                eprintln!(
+                    "Not submitting `{answer}`: answers for day {day} are blocked for another {}. Append `--wait` to wait and retry.",
+                    cooldown::format_wait(remaining)
+                );
+                return None;
+            }
+
+            println!(
+                "Waiting {} for the cooldown of day {day} to pass...",
+                cooldown::format_wait(remaining)
+            );
+            thread::sleep(remaining);
+        }
+
+        println!("Submitting result...");
+        let output = provider.submit(day, part, &answer);
+
+        match &output {
+            Ok(message) => {
+                println!("{message}");
+                record_cooldown(day, message);
+
+                if let Some(outcome) = Outcome::from_message(message) {
+                    if let Err(e) =
+                        submissions::append(&Submission::new(day, part, &answer, outcome))
+                    {
+                        eprintln!("Failed to record submission: {e}");
+                    }
+                    if outcome == Outcome::Correct {
+                        stars::record(day, part, true);
+                    }
+                }
+            }
+            Err(ProviderError::RateLimited(message)) if record_cooldown(day, message) => {
+                if wait {
+                    continue;
+                }
+                eprintln!("Failed to submit result: {message} Append `--wait` to wait and retry.");
+            }
+            Err(e) => eprintln!("Failed to submit result: {e}"),
+        }
+
+        return Some(output);
+    }
+
+    eprintln!("Failed to submit result: still rate limited after {MAX_SUBMIT_ATTEMPTS} attempts.");
+    None
+}
+
+/// Store the cooldown announced in a submission response. Returns whether there was one.
+fn record_cooldown(day: Day, message: &str) -> bool {
+    let Some(wait) = cooldown::parse_wait(message) else {
+        return false;
+    };
+
+    let mut cooldowns = Cooldowns::read_from_file();
+    cooldowns.set(day, cooldown::now(), wait);
+
+    if let Err(e) = cooldowns.store_file() {
+        eprintln!("Failed to store cooldown: {e}");
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    true
 }
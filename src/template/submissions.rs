@@ -0,0 +1,365 @@
+/// Append-only log of submitted answers and how Advent of Code responded to them.
+/// Used to refuse answers that are known to be wrong before they cost another lockout.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.jsonl";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Outcome {
+    /// Derive the outcome from the message of a submission.
+    /// Returns `None` for messages that do not judge the answer, e.g. when the part is already solved.
+    pub fn from_message(message: &str) -> Option<Self> {
+        if message.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if message.contains("That's not the right answer") {
+            Some(if message.contains("too high") {
+                Outcome::TooHigh
+            } else if message.contains("too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            })
+        } else {
+            None
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Outcome::Correct),
+            "wrong" => Ok(Outcome::Wrong),
+            "too_high" => Ok(Outcome::TooHigh),
+            "too_low" => Ok(Outcome::TooLow),
+            _ => Err(format!("unknown submission outcome `{s}`.")),
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str().replace('_', " "))
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    pub timestamp: u64,
+}
+
+impl Submission {
+    pub fn new(day: Day, part: u8, answer: &str, outcome: Outcome) -> Self {
+        Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Append a submission to the log file.
+pub fn append(submission: &Submission) -> Result<(), Error> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(SUBMISSIONS_FILE_PATH)?;
+
+    writeln!(
+        file,
+        "{}",
+        JsonValue::from(submission)
+            .stringify()
+            .map_err(Error::other)?
+    )
+}
+
+/// Read the submissions of one part of a day. Lines that can not be parsed are skipped.
+pub fn read_for_part(day: Day, part: u8) -> Vec<Submission> {
+    fs::read_to_string(SUBMISSIONS_FILE_PATH)
+        .map(|s| parse_submissions(&s))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| s.day == day && s.part == part)
+        .collect()
+}
+
+fn parse_submissions(s: &str) -> Vec<Submission> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let json = JsonValue::from_str(line).ok()?;
+            Submission::try_from(&json)
+                .map_err(|e| eprintln!("Skipping invalid submission: {e}"))
+                .ok()
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Why an answer is not submitted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyAccepted(String),
+    KnownWrong(Outcome),
+    OutOfBounds {
+        above: Option<i128>,
+        below: Option<i128>,
+    },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyAccepted(answer) => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Refusal::KnownWrong(outcome) => {
+                write!(f, "this answer was submitted before and was {outcome}.")
+            }
+            Refusal::OutOfBounds { above, below } => {
+                let bound = |x: &Option<i128>| x.map_or_else(|| "?".into(), |x| x.to_string());
+                write!(
+                    f,
+                    "earlier responses put the answer between {} and {}.",
+                    bound(above),
+                    bound(below)
+                )
+            }
+        }
+    }
+}
+
+/// Check an answer against earlier submissions of the same part.
+/// Numeric answers must lie strictly between the largest "too low" and the smallest "too high" answer.
+pub fn check(submissions: &[Submission], answer: &str) -> Result<(), Refusal> {
+    if let Some(correct) = submissions.iter().find(|s| s.outcome == Outcome::Correct) {
+        return Err(Refusal::AlreadyAccepted(correct.answer.clone()));
+    }
+
+    if let Some(previous) = submissions.iter().find(|s| s.answer == answer) {
+        return Err(Refusal::KnownWrong(previous.outcome));
+    }
+
+    let Ok(value) = answer.trim().parse::<i128>() else {
+        return Ok(());
+    };
+
+    let numbers = |outcome: Outcome| {
+        submissions
+            .iter()
+            .filter(move |s| s.outcome == outcome)
+            .filter_map(|s| s.answer.trim().parse::<i128>().ok())
+    };
+
+    let above = numbers(Outcome::TooLow).max();
+    let below = numbers(Outcome::TooHigh).min();
+
+    if above.is_some_and(|low| value <= low) || below.is_some_and(|high| value >= high) {
+        return Err(Refusal::OutOfBounds { above, below });
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.as_str().into()),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = match json.get("part").and_then(|v| v.get::<f64>()) {
+            Some(part) if *part == 1.0 => 1,
+            Some(part) if *part == 2.0 => 2,
+            _ => return Err("Expected submission.part to be 1 or 2.".into()),
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.outcome to be a string.")?
+            .parse()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer,
+            outcome,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{check, parse_submissions, Outcome, Refusal, Submission};
+    use crate::day;
+
+    fn submission(answer: &str, outcome: Outcome) -> Submission {
+        Submission {
+            day: day!(1),
+            part: 1,
+            answer: answer.into(),
+            outcome,
+            timestamp: 1_733_029_200,
+        }
+    }
+
+    #[test]
+    fn parses_outcomes() {
+        assert_eq!(
+            Outcome::from_message("That's the right answer! You are one gold star closer."),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(
+            Outcome::from_message("That's not the right answer; your answer is too high."),
+            Some(Outcome::TooHigh)
+        );
+        assert_eq!(
+            Outcome::from_message("That's not the right answer; your answer is too low."),
+            Some(Outcome::TooLow)
+        );
+        assert_eq!(
+            Outcome::from_message("That's not the right answer. If you're stuck, ..."),
+            Some(Outcome::Wrong)
+        );
+        assert_eq!(
+            Outcome::from_message("You don't seem to be solving the right level."),
+            None
+        );
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let submissions = [submission("abc", Outcome::Wrong)];
+        assert_eq!(
+            check(&submissions, "abc"),
+            Err(Refusal::KnownWrong(Outcome::Wrong))
+        );
+        assert_eq!(check(&submissions, "abd"), Ok(()));
+
+        let submissions = [submission("42", Outcome::Correct)];
+        assert_eq!(
+            check(&submissions, "43"),
+            Err(Refusal::AlreadyAccepted("42".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let submissions = [
+            submission("100", Outcome::TooLow),
+            submission("90", Outcome::TooLow),
+            submission("200", Outcome::TooHigh),
+        ];
+        let bounds = Refusal::OutOfBounds {
+            above: Some(100),
+            below: Some(200),
+        };
+
+        assert_eq!(check(&submissions, "150"), Ok(()));
+        assert_eq!(check(&submissions, "95"), Err(bounds));
+        assert!(check(&submissions, "200").is_err());
+        assert!(check(&submissions, "1000").is_err());
+        assert_eq!(check(&submissions, "abc"), Ok(()));
+        assert_eq!(check(&[], "1"), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submission = submission("42", Outcome::TooHigh);
+        let json = JsonValue::from(&submission).stringify().unwrap();
+        assert_eq!(
+            parse_submissions(&format!("{json}\n\nnot json\n")),
+            vec![submission]
+        );
+    }
+}
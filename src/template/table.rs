@@ -0,0 +1,66 @@
+/// Plain text tables for terminal output.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Align `rows` in columns separated by two spaces. The first row is the header and is printed in bold.
+pub fn format_table<T: AsRef<str>, const N: usize>(rows: &[[T; N]]) -> Vec<String> {
+    let widths: Vec<usize> = (0..N)
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].as_ref().chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell.as_ref()))
+                .collect();
+            let line = cells.join("  ");
+            let line = line.trim_end();
+
+            if i == 0 {
+                format!("{ANSI_BOLD}{line}{ANSI_RESET}")
+            } else {
+                line.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Print `rows` as a table, see [`format_table`].
+pub fn print_table<T: AsRef<str>, const N: usize>(rows: &[[T; N]]) {
+    for line in format_table(rows) {
+        println!("{line}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_table;
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn aligns_columns() {
+        let rows = [
+            ["Day", "Total", "Note"],
+            ["01", "1.50ms", ""],
+            ["02", "12.00ms", "ok"],
+        ];
+
+        assert_eq!(
+            format_table(&rows),
+            [
+                format!("{ANSI_BOLD}Day  Total    Note{ANSI_RESET}"),
+                "01   1.50ms".to_string(),
+                "02   12.00ms  ok".to_string(),
+            ]
+        );
+    }
+}
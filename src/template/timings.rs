@@ -1,24 +1,144 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    machine::{self, Machine},
+    Day,
+};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the `timings.json` schema written by [`Timings::store_file`].
+/// Version 1 files (without a `version` key) only stored per-part durations as display strings,
+/// version 2 files did not record which machine the timings were taken on.
+pub const TIMINGS_VERSION: u32 = 3;
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
     pub total_nanos: f64,
+    /// The cargo profile the timing was recorded with.
+    pub profile: Option<String>,
+    /// Label of the machine the timing was recorded on.
+    pub machine: Option<String>,
+    pub part_1_samples: Option<u64>,
+    pub part_2_samples: Option<u64>,
+    /// Peak resident memory of the solution process in KiB.
+    pub peak_memory_kb: Option<u64>,
+    /// Whether the answers match the known answers in `data/answers.json`, if there are any.
+    pub verified: Option<bool>,
+    /// Fingerprint of the solution, library and input the timing was recorded with.
+    pub source_hash: Option<String>,
+    /// Number of input files the day was benched against. Durations of days with several inputs are means.
+    pub inputs: Option<u64>,
+    pub part_1_max_nanos: Option<f64>,
+    pub part_2_max_nanos: Option<f64>,
+    /// Whether the bench was stopped with Ctrl-C, so the timing only covers the parts that ran. Not stored.
+    pub interrupted: bool,
 }
 
-/// Represents benchmark times for a set of days.
+impl Default for Timing {
+    /// An empty timing of the first day.
+    fn default() -> Self {
+        Self {
+            day: crate::day!(1),
+            part_1: None,
+            part_2: None,
+            part_1_nanos: None,
+            part_2_nanos: None,
+            total_nanos: 0_f64,
+            profile: None,
+            machine: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            peak_memory_kb: None,
+            verified: None,
+            source_hash: None,
+            inputs: None,
+            part_1_max_nanos: None,
+            part_2_max_nanos: None,
+            interrupted: false,
+        }
+    }
+}
+
+impl Timing {
+    /// Combine the timings of a day benched against several inputs into their mean and max durations.
+    /// A part is only aggregated if it was timed for every input.
+    pub fn aggregate(day: Day, timings: &[Timing]) -> Timing {
+        let part = |nanos: fn(&Timing) -> Option<f64>| {
+            let values: Vec<f64> = timings.iter().filter_map(nanos).collect();
+            if values.is_empty() || values.len() != timings.len() {
+                return (None, None);
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let max = values.iter().copied().fold(f64::MIN, f64::max);
+            (Some(mean), Some(max))
+        };
+
+        let (part_1_nanos, part_1_max_nanos) = part(|t| t.part_1_nanos);
+        let (part_2_nanos, part_2_max_nanos) = part(|t| t.part_2_nanos);
+
+        let samples = |samples: fn(&Timing) -> Option<u64>| {
+            timings.iter().filter_map(samples).reduce(|a, b| a + b)
+        };
+
+        Timing {
+            day,
+            part_1: part_1_nanos.map(format_nanos),
+            part_2: part_2_nanos.map(format_nanos),
+            part_1_nanos,
+            part_2_nanos,
+            total_nanos: part_1_nanos.unwrap_or_default() + part_2_nanos.unwrap_or_default(),
+            part_1_samples: samples(|t| t.part_1_samples),
+            part_2_samples: samples(|t| t.part_2_samples),
+            peak_memory_kb: timings.iter().filter_map(|t| t.peak_memory_kb).max(),
+            inputs: Some(timings.len() as u64),
+            part_1_max_nanos,
+            part_2_max_nanos,
+            ..Default::default()
+        }
+    }
+}
+
+/// A timing of a day with the given display durations, for the tests of modules that work with timings.
+/// The durations are parsed into nanoseconds and add up to the total.
+#[cfg(feature = "test_lib")]
+pub fn mock_timing(day: Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+    let part_1_nanos = part_1.and_then(parse_duration);
+    let part_2_nanos = part_2.and_then(parse_duration);
+
+    Timing {
+        day,
+        part_1: part_1.map(String::from),
+        part_2: part_2.map(String::from),
+        part_1_nanos,
+        part_2_nanos,
+        total_nanos: part_1_nanos.unwrap_or_default() + part_2_nanos.unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+/// Represents benchmark times for a set of days, possibly recorded on several machines.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    pub machines: Vec<Machine>,
 }
 
 impl Timings {
@@ -30,14 +150,23 @@ impl Timings {
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// A file that can not be read, e.g. because it was written by a newer version, is an error,
+    /// so that storing timings does not replace it.
+    pub fn read_from_file() -> Result<Self, String> {
+        Timings::read_from_path(Path::new(TIMINGS_FILE_PATH))
+    }
+
+    fn read_from_path(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Timings::try_from(contents),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(e.to_string()),
+        }
+        .map_err(|e| format!("\"{}\": {e}", path.display()))
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Timings of a day are only overwritten by timings recorded on the same machine.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -46,18 +175,66 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data
+                .iter()
+                .any(|t| t.day == timing.day && t.machine == timing.machine)
+            {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by(|a, b| a.day.cmp(&b.day).then_with(|| a.machine.cmp(&b.machine)));
+
+        let mut machines = new.machines.clone();
+        for machine in &self.machines {
+            if !machines.iter().any(|m| m.label == machine.label) {
+                machines.push(machine.clone());
+            }
+        }
+
+        Timings { data, machines }
+    }
+
+    /// Tag all timings as recorded on `machine`.
+    pub fn with_machine(mut self, machine: &Machine) -> Self {
+        for timing in &mut self.data {
+            timing.machine = Some(machine.label.clone());
+        }
+        self.machines = vec![machine.clone()];
+        self
+    }
+
+    /// The dataset recorded on the machine with the given label.
+    pub fn for_machine(&self, label: &str) -> Self {
+        Timings {
+            data: self
+                .data
+                .iter()
+                .filter(|t| t.machine.as_deref() == Some(label))
+                .cloned()
+                .collect(),
+            machines: self
+                .machines
+                .iter()
+                .filter(|m| m.label == label)
+                .cloned()
+                .collect(),
+        }
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().fold(0_f64, |acc, x| acc + x.total_nanos) / 1_000_000_f64
+    }
+
+    /// A day is stale if it was benched with different code or input. Timings without a hash are never stale.
+    pub fn is_day_stale(&self, day: Day, source_hash: &str) -> bool {
+        self.data.iter().any(|t| {
+            t.day == day
+                && t.source_hash
+                    .as_deref()
+                    .is_some_and(|hash| hash != source_hash)
+        })
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -67,17 +244,48 @@ impl Timings {
     }
 }
 
+/// Parse a formatted duration like `74.13ns` or `1.2ms` into nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Format nanoseconds the same way solutions print their durations.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos.round() as u64))
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
+
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "machines".into(),
+            JsonValue::Array(value.machines.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -88,20 +296,56 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: files written before the schema was versioned do not have this key.
+        let version = match json.get("version") {
+            Some(v) => v
+                .get::<f64>()
+                .copied()
+                .ok_or("expected `json.version` to be a number.")?,
+            None => 1_f64,
+        };
+
+        if version > f64::from(TIMINGS_VERSION) {
+            return Err(format!(
+                "unsupported timings version {version}, expected at most {TIMINGS_VERSION}."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
-        Ok(Timings {
-            data: json_data
+        // NOTE: timings before version 3 were not grouped by machine.
+        let machines = match json.get("machines") {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.machines` to be an array.")?
                 .iter()
-                .map(Timing::try_from)
+                .map(Machine::try_from)
                 .collect::<Result<_, _>>()?,
-        })
+            None => vec![],
+        };
+
+        let mut data: Vec<Timing> = json_data
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        // NOTE: timings before version 3 were recorded on the machine that stored them, assume it is this one.
+        if version < 3_f64 {
+            let label = machine::current_label();
+            for timing in data.iter_mut().filter(|t| t.machine.is_none()) {
+                timing.machine = Some(label.clone());
+            }
+        }
+
+        Ok(Timings { data, machines })
     }
 }
 
@@ -133,6 +377,89 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_nanos".into(),
+            match value.part_1_nanos {
+                Some(x) => JsonValue::Number(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "part_2_nanos".into(),
+            match value.part_2_nanos {
+                Some(x) => JsonValue::Number(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "profile".into(),
+            match value.profile.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "machine".into(),
+            match value.machine.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        let optional_number = |x: Option<u64>| match x {
+            Some(x) => JsonValue::Number(x as f64),
+            None => JsonValue::Null,
+        };
+
+        map.insert(
+            "part_1_samples".into(),
+            optional_number(value.part_1_samples),
+        );
+        map.insert(
+            "part_2_samples".into(),
+            optional_number(value.part_2_samples),
+        );
+        map.insert(
+            "peak_memory_kb".into(),
+            optional_number(value.peak_memory_kb),
+        );
+
+        map.insert(
+            "verified".into(),
+            match value.verified {
+                Some(x) => JsonValue::Boolean(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "source_hash".into(),
+            match value.source_hash.clone() {
+                Some(x) => JsonValue::String(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert("inputs".into(), optional_number(value.inputs));
+
+        let optional_nanos = |x: Option<f64>| match x {
+            Some(x) => JsonValue::Number(x),
+            None => JsonValue::Null,
+        };
+
+        map.insert(
+            "part_1_max_nanos".into(),
+            optional_nanos(value.part_1_max_nanos),
+        );
+        map.insert(
+            "part_2_max_nanos".into(),
+            optional_nanos(value.part_2_max_nanos),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +493,84 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings stored before profiles were recorded do not have this key.
+        let profile = match json.get("profile") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected timing.profile to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        let machine = match json.get("machine") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected timing.machine to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        // NOTE: version 1 timings only stored display strings, migrate them by parsing the strings.
+        let read_nanos = |key: &str, display: Option<&String>| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<f64>()
+                .copied()
+                .map(Some)
+                .ok_or(format!("Expected timing.{key} to be null or number.")),
+            _ => Ok(display.and_then(|s| parse_duration(s))),
+        };
+
+        let part_1_nanos = read_nanos("part_1_nanos", part_1)?;
+        let part_2_nanos = read_nanos("part_2_nanos", part_2)?;
+
+        let source_hash = match json.get("source_hash") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected timing.source_hash to be null or string.")?,
+            ),
+            _ => None,
+        };
+
+        // NOTE: samples, memory and verification status are optional and absent in older timings.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let read_count = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<f64>()
+                .map(|v| Some(*v as u64))
+                .ok_or(format!("Expected timing.{key} to be null or number.")),
+            _ => Ok(None),
+        };
+
+        let verified = match json.get("verified") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<bool>()
+                    .copied()
+                    .ok_or("Expected timing.verified to be null or boolean.")?,
+            ),
+            _ => None,
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_nanos,
+            part_2_nanos,
             total_nanos,
+            profile,
+            machine,
+            part_1_samples: read_count("part_1_samples")?,
+            part_2_samples: read_count("part_2_samples")?,
+            peak_memory_kb: read_count("peak_memory_kb")?,
+            verified,
+            source_hash,
+            inputs: read_count("inputs")?,
+            part_1_max_nanos: read_nanos("part_1_max_nanos", None)?,
+            part_2_max_nanos: read_nanos("part_2_max_nanos", None)?,
+            interrupted: false,
         })
     }
 }
@@ -181,35 +581,27 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{mock_timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
+                mock_timing(day!(1), Some("10ms"), Some("20ms")),
+                mock_timing(day!(2), Some("30ms"), Some("40ms")),
+                mock_timing(day!(4), Some("40ms"), None),
             ],
+            machines: vec![],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use std::{env, fs, process};
+
+        use crate::{
+            day,
+            template::machine,
+            template::timings::{Timings, TIMINGS_VERSION},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -223,6 +615,48 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_profile() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "profile": "bench" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.profile, Some("bench".to_string()));
+        }
+
+        #[test]
+        fn migrates_unversioned_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.13ns", "part_2": "1.5ms", "total_nanos": 1500074.13 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_nanos, Some(74.13));
+            assert_eq!(timing.part_2_nanos, Some(1_500_000_f64));
+        }
+
+        #[test]
+        fn handles_versioned_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_nanos": 1000123, "part_2_nanos": null, "total_nanos": 1000123 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_nanos, Some(1_000_123_f64));
+            assert_eq!(timing.part_2_nanos, None);
+            assert_eq!(timing.machine, Some(machine::current_label()));
+        }
+
+        #[test]
+        fn handles_timings_with_machines() {
+            let json = r#"{ "version": 3, "machines": [{ "label": "ci", "cpu": null, "cores": 4, "rustc": "rustc 1.83.0", "os": "linux x86_64" }], "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_nanos": 1000000, "part_2_nanos": null, "total_nanos": 1000000, "machine": "ci" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.machines.len(), 1);
+            assert_eq!(timings.machines[0].cores, Some(4));
+            assert_eq!(timings.data[0].machine, Some("ci".to_string()));
+        }
+
+        #[test]
+        fn rejects_newer_versions() {
+            let json = format!(r#"{{ "version": {}, "data": [] }}"#, TIMINGS_VERSION + 1);
+            assert!(Timings::try_from(json).is_err());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -230,6 +664,22 @@ mod tests {
             assert_eq!(timings.data.len(), 0);
         }
 
+        #[test]
+        fn only_defaults_missing_files() {
+            let dir = env::temp_dir().join(format!("aoc-timings-{}", process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("timings.json");
+
+            assert_eq!(Timings::read_from_path(&path).unwrap().data.len(), 0);
+
+            let json = format!(r#"{{ "version": {}, "data": [] }}"#, TIMINGS_VERSION + 1);
+            fs::write(&path, json).unwrap();
+            let err = Timings::read_from_path(&path).unwrap_err();
+            fs::remove_dir_all(&dir).unwrap();
+
+            assert!(err.contains("unsupported timings version"));
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
@@ -247,9 +697,41 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{Timings, TIMINGS_VERSION};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_versioned_timings() {
+            let value = JsonValue::from(get_mock_timings());
+            let version = value
+                .get::<HashMap<String, JsonValue>>()
+                .unwrap()
+                .get("version")
+                .unwrap()
+                .get::<f64>()
+                .copied();
+            assert_eq!(version, Some(f64::from(TIMINGS_VERSION)));
+
+            let timings = Timings::try_from(value.stringify().unwrap()).unwrap();
+            assert_eq!(timings.data[0].part_1_nanos, Some(1e7));
+            assert_eq!(timings.data[2].part_2_nanos, None);
+            assert_eq!(timings.data[0].inputs, None);
+        }
+
+        #[test]
+        fn roundtrips_input_aggregates() {
+            let mut timings = get_mock_timings();
+            timings.data[0].inputs = Some(3);
+            timings.data[0].part_1_max_nanos = Some(2e7);
+
+            let value = JsonValue::from(timings);
+            let timings = Timings::try_from(value.stringify().unwrap()).unwrap();
+            assert_eq!(timings.data[0].inputs, Some(3));
+            assert_eq!(timings.data[0].part_1_max_nanos, Some(2e7));
+            assert_eq!(timings.data[0].part_2_max_nanos, None);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -271,56 +753,128 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{mock_timing, Timings},
         };
 
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
-                }],
+                data: vec![mock_timing(day!(1), Some("1ms"), Some("2ms"))],
+                machines: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
-                }],
+                data: vec![mock_timing(day!(1), Some("1ms"), None)],
+                machines: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
+            let timings = Timings {
+                data: vec![mock_timing(day!(1), None, None)],
+                machines: vec![],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod is_day_stale {
+        use crate::{
+            day,
+            template::timings::{mock_timing, Timing, Timings},
+        };
+
+        #[test]
+        fn handles_changed_hashes() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    source_hash: Some("a1b2".into()),
+                    ..mock_timing(day!(1), Some("1ms"), Some("2ms"))
                 }],
+                machines: vec![],
             };
+            assert!(timings.is_day_stale(day!(1), "ffff"));
+            assert!(!timings.is_day_stale(day!(1), "a1b2"));
+            assert!(!timings.is_day_stale(day!(2), "ffff"));
+        }
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+        #[test]
+        fn handles_missing_hashes() {
+            let timings = Timings {
+                data: vec![mock_timing(day!(1), Some("1ms"), Some("2ms"))],
+                machines: vec![],
+            };
+            assert!(!timings.is_day_stale(day!(1), "ffff"));
+        }
+    }
+
+    mod aggregate {
+        use crate::{
+            day,
+            template::timings::{mock_timing, Timing},
+        };
+
+        #[test]
+        fn aggregates_mean_and_max() {
+            let timing = Timing::aggregate(
+                day!(1),
+                &[
+                    Timing {
+                        part_1_samples: Some(100),
+                        peak_memory_kb: Some(1024),
+                        ..mock_timing(day!(1), Some("1ms"), Some("4ms"))
+                    },
+                    Timing {
+                        part_1_samples: Some(100),
+                        peak_memory_kb: Some(512),
+                        ..mock_timing(day!(1), Some("3ms"), Some("2ms"))
+                    },
+                ],
+            );
+            assert_eq!(timing.part_1_nanos, Some(2e6));
+            assert_eq!(timing.part_1_max_nanos, Some(3e6));
+            assert_eq!(timing.part_2_nanos, Some(3e6));
+            assert_eq!(timing.part_2_max_nanos, Some(4e6));
+            assert_eq!(timing.part_1.as_deref(), Some("2.0ms"));
+            assert_eq!(timing.total_nanos, 5e6);
+            assert_eq!(timing.part_1_samples, Some(200));
+            assert_eq!(timing.peak_memory_kb, Some(1024));
+            assert_eq!(timing.inputs, Some(2));
+        }
+
+        #[test]
+        fn skips_parts_missing_for_an_input() {
+            let timing = Timing::aggregate(
+                day!(1),
+                &[
+                    mock_timing(day!(1), Some("1ms"), Some("4ms")),
+                    mock_timing(day!(1), Some("3ms"), None),
+                ],
+            );
+            assert_eq!(timing.part_1_nanos, Some(2e6));
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.part_2_nanos, None);
+            assert_eq!(timing.part_2_max_nanos, None);
+            assert_eq!(timing.total_nanos, 2e6);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                machine::Machine,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -331,10 +885,9 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
+                machines: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -351,10 +904,9 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
+                machines: vec![],
             };
             let merged = timings.merge(&other);
 
@@ -365,6 +917,37 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_timings_of_other_machines() {
+            let machine = Machine {
+                label: "ci".into(),
+                cpu: None,
+                cores: Some(2),
+                rustc: None,
+                os: "linux x86_64".into(),
+            };
+
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    ..Default::default()
+                }],
+                machines: vec![],
+            }
+            .with_machine(&machine);
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.machines, vec![machine]);
+
+            let ci = merged.for_machine("ci");
+            assert_eq!(ci.data.len(), 1);
+            assert_eq!(ci.data[0].day, day!(2));
+            assert_eq!(ci.data[0].total_nanos, 0_f64);
+            assert!(merged.for_machine("laptop").data.is_empty());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();